@@ -389,4 +389,297 @@ pub fn quick_select(arr: &mut [i64], k: usize) -> i64 {
     } else {
         pivot
     }
-} 
\ No newline at end of file
+} 
+
+// 各 i について、keep(arr[j], arr[i]) を満たす最も近い j を単調スタックで求める（O(n)）
+// forward なら j < i、そうでなければ j > i を探す
+fn nearest_by<T, F>(arr: &[T], forward: bool, keep: F) -> Vec<Option<usize>>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let n = arr.len();
+    let mut result = vec![None; n];
+    let mut stack: Vec<usize> = Vec::new();
+    let order: Box<dyn Iterator<Item = usize>> = if forward {
+        Box::new(0..n)
+    } else {
+        Box::new((0..n).rev())
+    };
+    for i in order {
+        while let Some(&top) = stack.last() {
+            if keep(&arr[top], &arr[i]) {
+                break;
+            }
+            stack.pop();
+        }
+        result[i] = stack.last().copied();
+        stack.push(i);
+    }
+    result
+}
+
+// 左側で最も近い arr[j] < arr[i] の j（strict が false なら arr[j] <= arr[i]）
+pub fn previous_smaller<T: Ord>(arr: &[T], strict: bool) -> Vec<Option<usize>> {
+    nearest_by(arr, true, |a, b| a < b || (!strict && a == b))
+}
+
+// 右側で最も近い arr[j] < arr[i] の j（strict が false なら arr[j] <= arr[i]）
+pub fn next_smaller<T: Ord>(arr: &[T], strict: bool) -> Vec<Option<usize>> {
+    nearest_by(arr, false, |a, b| a < b || (!strict && a == b))
+}
+
+// 左側で最も近い arr[j] > arr[i] の j（strict が false なら arr[j] >= arr[i]）
+pub fn previous_greater<T: Ord>(arr: &[T], strict: bool) -> Vec<Option<usize>> {
+    nearest_by(arr, true, |a, b| a > b || (!strict && a == b))
+}
+
+// 右側で最も近い arr[j] > arr[i] の j（strict が false なら arr[j] >= arr[i]）
+pub fn next_greater<T: Ord>(arr: &[T], strict: bool) -> Vec<Option<usize>> {
+    nearest_by(arr, false, |a, b| a > b || (!strict && a == b))
+}
+
+// ヒストグラム中の最大長方形の面積（O(n)）
+pub fn largest_rectangle(heights: &[i64]) -> i64 {
+    let n = heights.len();
+    let left = previous_smaller(heights, true);
+    let right = next_smaller(heights, true);
+    (0..n)
+        .map(|i| {
+            let l = left[i].map_or(0, |j| j + 1);
+            let r = right[i].unwrap_or(n);
+            heights[i] * (r - l) as i64
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// Cartesian Tree（値が最小の要素を根とし、左右の部分木が左右の区間に対応する二分木）
+// 同じ値の要素は左にあるものが祖先になる
+pub struct CartesianTree {
+    pub root: Option<usize>,
+    pub parent: Vec<Option<usize>>,
+    pub left: Vec<Option<usize>>,
+    pub right: Vec<Option<usize>>,
+}
+
+impl CartesianTree {
+    // 最小値を根とする Cartesian Tree を構築（O(n)）
+    pub fn new<T: Ord>(arr: &[T]) -> Self {
+        Self::build(arr, |a, b| a < b)
+    }
+
+    // 最大値を根とする Cartesian Tree を構築（O(n)）
+    pub fn new_max<T: Ord>(arr: &[T]) -> Self {
+        Self::build(arr, |a, b| a > b)
+    }
+
+    fn build<T, F>(arr: &[T], before: F) -> Self
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let n = arr.len();
+        let mut parent = vec![None; n];
+        let mut left = vec![None; n];
+        let mut right = vec![None; n];
+        // 右の背骨をスタックで管理する
+        let mut stack: Vec<usize> = Vec::new();
+        for i in 0..n {
+            let mut last = None;
+            while let Some(&top) = stack.last() {
+                if !before(&arr[i], &arr[top]) {
+                    break;
+                }
+                last = stack.pop();
+            }
+            if let Some(child) = last {
+                left[i] = Some(child);
+                parent[child] = Some(i);
+            }
+            if let Some(&top) = stack.last() {
+                right[top] = Some(i);
+                parent[i] = Some(top);
+            }
+            stack.push(i);
+        }
+        CartesianTree { root: stack.first().copied(), parent, left, right }
+    }
+}
+
+// 座標圧縮（値 <-> 0-indexed の番号を相互に変換する）
+// 番号は 0..len() に収まるので FenwickTree::new(comp.len()) などにそのまま渡せる
+pub struct Compressor<T: Ord + Clone> {
+    values: Vec<T>,
+}
+
+impl<T: Ord + Clone> Compressor<T> {
+    // 値の列から作成（ソートして重複を除く）
+    pub fn new<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut values: Vec<T> = iter.into_iter().collect();
+        values.sort();
+        values.dedup();
+        Compressor { values }
+    }
+
+    // 複数の値の列をまとめて圧縮する
+    pub fn from_iters<I, J>(iters: I) -> Self
+    where
+        I: IntoIterator<Item = J>,
+        J: IntoIterator<Item = T>,
+    {
+        Self::new(iters.into_iter().flatten())
+    }
+
+    // 異なる値の個数
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // 値の番号を取得（含まれない値なら None）
+    pub fn index_of(&self, value: &T) -> Option<usize> {
+        self.values.binary_search(value).ok()
+    }
+
+    // 番号に対応する値を取得
+    pub fn value_at(&self, index: usize) -> &T {
+        &self.values[index]
+    }
+
+    // value 以上の最小の値の番号（なければ len()）
+    pub fn lower_bound(&self, value: &T) -> usize {
+        self.values.partition_point(|v| v < value)
+    }
+
+    // value より大きい最小の値の番号（なければ len()）
+    pub fn upper_bound(&self, value: &T) -> usize {
+        self.values.partition_point(|v| v <= value)
+    }
+
+    // 配列の各要素を番号に変換する（要素はすべて含まれている必要がある）
+    pub fn compress(&self, arr: &[T]) -> Vec<usize> {
+        arr.iter()
+            .map(|v| self.index_of(v).expect("value is not in the compressor"))
+            .collect()
+    }
+
+    // 圧縮後の値を昇順に並べたスライス
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+
+    // 重複が多い小さな配列
+    fn random_arrays(rng: &mut XorShift) -> Vec<Vec<i64>> {
+        (0..300)
+            .map(|_| {
+                let n = rng.next(12);
+                (0..n).map(|_| rng.range(0, 4)).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest_against_naive() {
+        let mut rng = XorShift::default();
+        for arr in random_arrays(&mut rng) {
+            let n = arr.len();
+            for strict in [true, false] {
+                let smaller = |a: i64, b: i64| a < b || (!strict && a == b);
+                let greater = |a: i64, b: i64| a > b || (!strict && a == b);
+                let prev = |f: &dyn Fn(i64, i64) -> bool| -> Vec<Option<usize>> {
+                    (0..n).map(|i| (0..i).rev().find(|&j| f(arr[j], arr[i]))).collect()
+                };
+                let next = |f: &dyn Fn(i64, i64) -> bool| -> Vec<Option<usize>> {
+                    (0..n).map(|i| (i + 1..n).find(|&j| f(arr[j], arr[i]))).collect()
+                };
+                assert_eq!(previous_smaller(&arr, strict), prev(&smaller));
+                assert_eq!(next_smaller(&arr, strict), next(&smaller));
+                assert_eq!(previous_greater(&arr, strict), prev(&greater));
+                assert_eq!(next_greater(&arr, strict), next(&greater));
+            }
+            let expected = (0..n)
+                .flat_map(|l| (l..n).map(move |r| (l, r)))
+                .map(|(l, r)| arr[l..=r].iter().min().unwrap() * (r - l + 1) as i64)
+                .max()
+                .unwrap_or(0);
+            assert_eq!(largest_rectangle(&arr), expected);
+        }
+    }
+
+    // 親子関係の整合性、ヒープ条件、中順走査が 0..n になること、同じ値なら左が祖先であることを確認する
+    fn check_cartesian_tree(arr: &[i64], tree: &CartesianTree, before: fn(i64, i64) -> bool) {
+        let n = arr.len();
+        assert_eq!(tree.root.is_none(), n == 0);
+        let mut order = Vec::new();
+        let mut stack = Vec::new();
+        let mut cur = tree.root;
+        while cur.is_some() || !stack.is_empty() {
+            while let Some(v) = cur {
+                stack.push(v);
+                cur = tree.left[v];
+            }
+            let v = stack.pop().unwrap();
+            order.push(v);
+            cur = tree.right[v];
+        }
+        assert_eq!(order, (0..n).collect::<Vec<_>>());
+        for v in 0..n {
+            for child in [tree.left[v], tree.right[v]].into_iter().flatten() {
+                assert_eq!(tree.parent[child], Some(v));
+                assert!(!before(arr[child], arr[v]));
+                if arr[child] == arr[v] {
+                    assert!(v < child);
+                }
+            }
+        }
+        if let Some(root) = tree.root {
+            assert_eq!(tree.parent[root], None);
+        }
+    }
+
+    #[test]
+    fn test_cartesian_tree() {
+        let mut rng = XorShift::new(2463534242);
+        for arr in random_arrays(&mut rng) {
+            check_cartesian_tree(&arr, &CartesianTree::new(&arr), |a, b| a < b);
+            check_cartesian_tree(&arr, &CartesianTree::new_max(&arr), |a, b| a > b);
+        }
+        let tree = CartesianTree::new(&[2, 1, 1, 3, 1]);
+        assert_eq!(tree.root, Some(1));
+        assert_eq!(tree.right[1], Some(2));
+        assert_eq!(tree.right[2], Some(4));
+        assert_eq!(tree.left[4], Some(3));
+    }
+
+    #[test]
+    fn test_compressor() {
+        let comp = Compressor::from_iters([vec![30, 10, 50], vec![], vec![10, 20, 30]]);
+        assert_eq!(comp.len(), 4);
+        assert_eq!(comp.values(), &[10, 20, 30, 50]);
+        assert_eq!(comp.index_of(&30), Some(2));
+        assert_eq!(comp.index_of(&40), None);
+        assert_eq!(comp.index_of(&0), None);
+        assert_eq!(comp.index_of(&60), None);
+        assert_eq!(*comp.value_at(3), 50);
+        // 両端と、含まれない値・含まれる値での境界
+        assert_eq!((comp.lower_bound(&0), comp.upper_bound(&0)), (0, 0));
+        assert_eq!((comp.lower_bound(&10), comp.upper_bound(&10)), (0, 1));
+        assert_eq!((comp.lower_bound(&40), comp.upper_bound(&40)), (3, 3));
+        assert_eq!((comp.lower_bound(&50), comp.upper_bound(&50)), (3, 4));
+        assert_eq!((comp.lower_bound(&60), comp.upper_bound(&60)), (4, 4));
+        assert_eq!(comp.compress(&[50, 10, 10, 20]), vec![3, 0, 0, 1]);
+
+        let empty: Compressor<i64> = Compressor::new(vec![]);
+        assert!(empty.is_empty());
+        assert_eq!((empty.lower_bound(&0), empty.upper_bound(&0)), (0, 0));
+        assert!(empty.compress(&[]).is_empty());
+    }
+}
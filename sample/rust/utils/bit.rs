@@ -316,4 +316,537 @@ pub fn reverse_bits(n: i64) -> i64 {
 // バイトの順序を逆にする（エンディアン変換）
 pub fn swap_bytes(n: i64) -> i64 {
     n.swap_bytes()
-}
\ No newline at end of file
+}
+
+// バイナリトライ（非負整数の多重集合に対する XOR クエリ）
+// 全要素に XOR を作用させる操作を遅延値として持つ
+pub struct BinaryTrie {
+    // 子ノード（存在しない場合は usize::MAX）
+    children: Vec<[usize; 2]>,
+    // ノードの部分木に含まれる要素数
+    count: Vec<usize>,
+    bits: u32,
+    lazy: u64,
+}
+
+impl BinaryTrie {
+    // 値が bits ビット（bits <= 64）に収まる多重集合を作成
+    pub fn new(bits: u32) -> Self {
+        assert!(bits <= 64);
+        BinaryTrie {
+            children: vec![[usize::MAX; 2]],
+            count: vec![0],
+            bits,
+            lazy: 0,
+        }
+    }
+
+    // 要素数
+    pub fn len(&self) -> usize {
+        self.count[0]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn bit(x: u64, b: u32) -> usize {
+        ((x >> b) & 1) as usize
+    }
+
+    // 全要素に x を XOR する（O(1)）
+    pub fn xor_all(&mut self, x: u64) {
+        self.lazy ^= x;
+    }
+
+    // x を 1 個追加
+    pub fn insert(&mut self, x: u64) {
+        assert!(self.bits == 64 || x >> self.bits == 0);
+        let key = x ^ self.lazy;
+        let mut node = 0;
+        self.count[node] += 1;
+        for b in (0..self.bits).rev() {
+            let c = Self::bit(key, b);
+            if self.children[node][c] == usize::MAX {
+                self.children.push([usize::MAX; 2]);
+                self.count.push(0);
+                self.children[node][c] = self.children.len() - 1;
+            }
+            node = self.children[node][c];
+            self.count[node] += 1;
+        }
+    }
+
+    // x を 1 個削除（含まれていなければ false）
+    pub fn erase(&mut self, x: u64) -> bool {
+        if self.count(x) == 0 {
+            return false;
+        }
+        let key = x ^ self.lazy;
+        let mut node = 0;
+        self.count[node] -= 1;
+        for b in (0..self.bits).rev() {
+            node = self.children[node][Self::bit(key, b)];
+            self.count[node] -= 1;
+        }
+        true
+    }
+
+    // x の個数
+    pub fn count(&self, x: u64) -> usize {
+        if self.bits < 64 && x >> self.bits != 0 {
+            return 0;
+        }
+        let key = x ^ self.lazy;
+        let mut node = 0;
+        for b in (0..self.bits).rev() {
+            node = self.children[node][Self::bit(key, b)];
+            if node == usize::MAX {
+                return 0;
+            }
+        }
+        self.count[node]
+    }
+
+    fn has(&self, node: usize, c: usize) -> bool {
+        let child = self.children[node][c];
+        child != usize::MAX && self.count[child] > 0
+    }
+
+    // 集合内の a について a ^ x の最大値（空なら None）
+    pub fn max_xor(&self, x: u64) -> Option<u64> {
+        self.min_xor(x ^ self.mask()).map(|v| v ^ self.mask())
+    }
+
+    // 集合内の a について a ^ x の最小値（空なら None）
+    // x の bits ビット目以上はそのまま結果に残る
+    pub fn min_xor(&self, x: u64) -> Option<u64> {
+        if self.is_empty() {
+            return None;
+        }
+        let t = x ^ self.lazy;
+        let mut node = 0;
+        let mut result = 0;
+        for b in (0..self.bits).rev() {
+            let c = Self::bit(t, b);
+            if self.has(node, c) {
+                node = self.children[node][c];
+            } else {
+                node = self.children[node][c ^ 1];
+                result |= 1 << b;
+            }
+        }
+        Some(result | (x & !self.mask()))
+    }
+
+    fn mask(&self) -> u64 {
+        if self.bits == 64 {
+            u64::MAX
+        } else {
+            (1u64 << self.bits) - 1
+        }
+    }
+
+    // k 番目（0-indexed）に小さい要素（k >= len なら None）
+    pub fn kth_smallest(&self, mut k: usize) -> Option<u64> {
+        if k >= self.len() {
+            return None;
+        }
+        let mut node = 0;
+        let mut result = 0;
+        for b in (0..self.bits).rev() {
+            // 遅延値のビットが立っていれば、値としての 0 側は格納上の 1 側
+            let zero = Self::bit(self.lazy, b);
+            let left = self.children[node][zero];
+            let left_count = if left == usize::MAX { 0 } else { self.count[left] };
+            if k < left_count {
+                node = left;
+            } else {
+                k -= left_count;
+                node = self.children[node][zero ^ 1];
+                result |= 1 << b;
+            }
+        }
+        Some(result)
+    }
+
+    // 集合内の a のうち a ^ x < k を満たすものの個数
+    pub fn count_less_xor(&self, x: u64, k: u64) -> usize {
+        // bits ビット目以上の部分は a ^ x では x のものになるので、そこで大小が決まる
+        let (xh, kh) = (x & !self.mask(), k & !self.mask());
+        if xh != kh {
+            return if xh < kh { self.len() } else { 0 };
+        }
+        let t = x ^ self.lazy;
+        let mut node = 0;
+        let mut result = 0;
+        for b in (0..self.bits).rev() {
+            let c = Self::bit(t, b);
+            if Self::bit(k, b) == 1 {
+                // このビットが 0 になる側はすべて k 未満
+                let child = self.children[node][c];
+                if child != usize::MAX {
+                    result += self.count[child];
+                }
+                node = self.children[node][c ^ 1];
+            } else {
+                node = self.children[node][c];
+            }
+            if node == usize::MAX {
+                return result;
+            }
+        }
+        result
+    }
+}
+
+// 固定長のビット集合（64 ビットごとにまとめて演算する）
+// 部分和 DP や推移閉包などの O(N^3 / 64) 系の高速化に使う
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+    n: usize,
+}
+
+impl BitSet {
+    // 長さ n のすべて 0 のビット集合を作成
+    pub fn new(n: usize) -> Self {
+        BitSet { words: vec![0; n.div_ceil(64)], n }
+    }
+
+    // ビット数
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    // i 番目のビットが立っているか
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.n);
+        (self.words[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    // i 番目のビットを value にする
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.n);
+        if value {
+            self.words[i / 64] |= 1 << (i % 64);
+        } else {
+            self.words[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    // i 番目のビットを反転する
+    pub fn flip(&mut self, i: usize) {
+        assert!(i < self.n);
+        self.words[i / 64] ^= 1 << (i % 64);
+    }
+
+    // すべてのビットを 0 にする
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+
+    // 範囲外（n 以上）のビットを 0 に保つ
+    fn trim(&mut self) {
+        let rest = self.n % 64;
+        if rest > 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1u64 << rest) - 1;
+            }
+        }
+    }
+
+    // 立っているビットの個数
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // i より大きい位置で最初に立っているビット（C++ の _Find_next 相当）
+    pub fn first_set_after(&self, i: usize) -> Option<usize> {
+        let start = i + 1;
+        if start >= self.n {
+            return None;
+        }
+        let mut w = start / 64;
+        let mut word = self.words[w] & (u64::MAX << (start % 64));
+        loop {
+            if word != 0 {
+                return Some(w * 64 + word.trailing_zeros() as usize);
+            }
+            w += 1;
+            if w == self.words.len() {
+                return None;
+            }
+            word = self.words[w];
+        }
+    }
+
+    // 最初に立っているビット
+    pub fn first_set(&self) -> Option<usize> {
+        self.words
+            .iter()
+            .position(|&w| w != 0)
+            .map(|w| w * 64 + self.words[w].trailing_zeros() as usize)
+    }
+
+    // 立っているビットの位置を昇順に列挙する
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(w, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(w * 64 + bit)
+            })
+        })
+    }
+
+    // self |= self << k（部分和 DP の遷移 dp |= dp << a を 1 行で書ける）
+    pub fn or_shifted(&mut self, k: usize) {
+        let (word_shift, bit_shift) = (k / 64, k % 64);
+        for i in (word_shift..self.words.len()).rev() {
+            let mut shifted = self.words[i - word_shift] << bit_shift;
+            if bit_shift > 0 && i > word_shift {
+                shifted |= self.words[i - word_shift - 1] >> (64 - bit_shift);
+            }
+            self.words[i] |= shifted;
+        }
+        self.trim();
+    }
+
+    // 左シフト（添字が大きくなる方向、はみ出たビットは捨てる）
+    pub fn shl(&self, k: usize) -> BitSet {
+        let mut result = BitSet::new(self.n);
+        let (word_shift, bit_shift) = (k / 64, k % 64);
+        for i in word_shift..self.words.len() {
+            let mut word = self.words[i - word_shift] << bit_shift;
+            if bit_shift > 0 && i > word_shift {
+                word |= self.words[i - word_shift - 1] >> (64 - bit_shift);
+            }
+            result.words[i] = word;
+        }
+        result.trim();
+        result
+    }
+
+    // 右シフト（添字が小さくなる方向）
+    pub fn shr(&self, k: usize) -> BitSet {
+        let mut result = BitSet::new(self.n);
+        let (word_shift, bit_shift) = (k / 64, k % 64);
+        let len = self.words.len();
+        for i in 0..len.saturating_sub(word_shift) {
+            let mut word = self.words[i + word_shift] >> bit_shift;
+            if bit_shift > 0 && i + word_shift + 1 < len {
+                word |= self.words[i + word_shift + 1] << (64 - bit_shift);
+            }
+            result.words[i] = word;
+        }
+        result
+    }
+}
+
+impl std::ops::BitAndAssign<&BitSet> for BitSet {
+    fn bitand_assign(&mut self, rhs: &BitSet) {
+        assert_eq!(self.n, rhs.n);
+        self.words.iter_mut().zip(&rhs.words).for_each(|(a, b)| *a &= b);
+    }
+}
+
+impl std::ops::BitOrAssign<&BitSet> for BitSet {
+    fn bitor_assign(&mut self, rhs: &BitSet) {
+        assert_eq!(self.n, rhs.n);
+        self.words.iter_mut().zip(&rhs.words).for_each(|(a, b)| *a |= b);
+    }
+}
+
+impl std::ops::BitXorAssign<&BitSet> for BitSet {
+    fn bitxor_assign(&mut self, rhs: &BitSet) {
+        assert_eq!(self.n, rhs.n);
+        self.words.iter_mut().zip(&rhs.words).for_each(|(a, b)| *a ^= b);
+    }
+}
+
+impl std::ops::BitAnd for &BitSet {
+    type Output = BitSet;
+    fn bitand(self, rhs: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result &= rhs;
+        result
+    }
+}
+
+impl std::ops::BitOr for &BitSet {
+    type Output = BitSet;
+    fn bitor(self, rhs: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result |= rhs;
+        result
+    }
+}
+
+impl std::ops::BitXor for &BitSet {
+    type Output = BitSet;
+    fn bitxor(self, rhs: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result ^= rhs;
+        result
+    }
+}
+
+impl std::ops::Shl<usize> for &BitSet {
+    type Output = BitSet;
+    fn shl(self, k: usize) -> BitSet {
+        BitSet::shl(self, k)
+    }
+}
+
+impl std::ops::Shr<usize> for &BitSet {
+    type Output = BitSet;
+    fn shr(self, k: usize) -> BitSet {
+        BitSet::shr(self, k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+
+    #[test]
+    fn test_binary_trie_against_vec() {
+        let mut rng = XorShift::default();
+        for bits in [1, 3, 10, 64] {
+            let mask = if bits == 64 { u64::MAX } else { (1u64 << bits) - 1 };
+            // 重複が出やすいように値は少数の候補から選ぶ
+            let pool: Vec<u64> = (0..6).map(|_| rng.next_u64() & mask).collect();
+            let mut trie = BinaryTrie::new(bits);
+            let mut naive: Vec<u64> = Vec::new();
+            for _ in 0..1000 {
+                let x = pool[rng.next(pool.len())];
+                match rng.next(5) {
+                    0 | 1 => {
+                        trie.insert(x);
+                        naive.push(x);
+                    }
+                    2 => {
+                        let pos = naive.iter().position(|&a| a == x);
+                        assert_eq!(trie.erase(x), pos.is_some());
+                        if let Some(i) = pos {
+                            naive.swap_remove(i);
+                        }
+                    }
+                    3 => {
+                        let y = rng.next_u64() & mask;
+                        trie.xor_all(y);
+                        naive.iter_mut().for_each(|a| *a ^= y);
+                    }
+                    _ => {
+                        let x = x ^ (rng.next_u64() & mask & 3);
+                        let k = rng.next_u64() & mask;
+                        assert_eq!(trie.len(), naive.len());
+                        assert_eq!(trie.count(x), naive.iter().filter(|&&a| a == x).count());
+                        assert_eq!(trie.max_xor(x), naive.iter().map(|&a| a ^ x).max());
+                        assert_eq!(trie.min_xor(x), naive.iter().map(|&a| a ^ x).min());
+                        assert_eq!(trie.count_less_xor(x, k), naive.iter().filter(|&&a| a ^ x < k).count());
+                        assert_eq!(trie.count_less_xor(x, mask), naive.iter().filter(|&&a| a ^ x < mask).count());
+                        let mut sorted = naive.clone();
+                        sorted.sort_unstable();
+                        for (i, &a) in sorted.iter().enumerate() {
+                            assert_eq!(trie.kth_smallest(i), Some(a));
+                        }
+                        assert_eq!(trie.kth_smallest(sorted.len()), None);
+                        // bits ビットに収まらない x と k
+                        let x = x | (rng.next_u64() & !mask);
+                        let k = if rng.next(2) == 0 { rng.next_u64() } else { (x & !mask) | (rng.next_u64() & mask) };
+                        assert_eq!(trie.max_xor(x), naive.iter().map(|&a| a ^ x).max());
+                        assert_eq!(trie.min_xor(x), naive.iter().map(|&a| a ^ x).min());
+                        assert_eq!(trie.count_less_xor(x, k), naive.iter().filter(|&&a| a ^ x < k).count());
+                    }
+                }
+            }
+            if bits < 64 {
+                // 範囲外の値は含まれず、範囲外の k 未満にはすべてが入る
+                assert_eq!(trie.count(mask + 1), 0);
+                assert_eq!(trie.count_less_xor(0, mask + 1), naive.len());
+            }
+        }
+        let mut trie = BinaryTrie::new(3);
+        trie.insert(5);
+        assert_eq!(trie.max_xor(8), Some(13));
+        assert_eq!(trie.min_xor(8), Some(13));
+        assert_eq!(trie.count_less_xor(8, 14), 1);
+        assert_eq!(trie.count_less_xor(8, 13), 0);
+        assert_eq!(trie.count_less_xor(16, 13), 0);
+    }
+
+    fn to_bitset(bits: &[bool]) -> BitSet {
+        let mut b = BitSet::new(bits.len());
+        for (i, &x) in bits.iter().enumerate() {
+            b.set(i, x);
+        }
+        b
+    }
+
+    fn check(b: &BitSet, naive: &[bool]) {
+        assert_eq!(b.len(), naive.len());
+        assert_eq!(*b, to_bitset(naive));
+        assert_eq!(b.count_ones(), naive.iter().filter(|&&x| x).count());
+        let ones: Vec<usize> = (0..naive.len()).filter(|&i| naive[i]).collect();
+        assert_eq!(b.iter_ones().collect::<Vec<_>>(), ones);
+        assert_eq!(b.first_set(), ones.first().copied());
+        for (i, &x) in naive.iter().enumerate() {
+            assert_eq!(b.get(i), x);
+            assert_eq!(b.first_set_after(i), ones.iter().copied().find(|&j| j > i));
+        }
+    }
+
+    #[test]
+    fn test_bitset_shifts_against_vec() {
+        let mut rng = XorShift::new(2463534242);
+        for n in [1, 5, 63, 64, 65, 128, 130, 200] {
+            for _ in 0..5 {
+                let naive: Vec<bool> = (0..n).map(|_| rng.next(3) == 0).collect();
+                let b = to_bitset(&naive);
+                check(&b, &naive);
+                for k in [0, 1, 63, 64, 65, 127, 128, 129, n - 1, n, n + 1, 3 * n, rng.next(n)] {
+                    let shl: Vec<bool> = (0..n).map(|i| i >= k && naive[i - k]).collect();
+                    let shr: Vec<bool> = (0..n).map(|i| i + k < n && naive[i + k]).collect();
+                    // n が 64 の倍数でないときは、はみ出たビットが残らないこと
+                    check(&(&b << k), &shl);
+                    check(&(&b >> k), &shr);
+                    let mut or = b.clone();
+                    or.or_shifted(k);
+                    let expected: Vec<bool> = naive.iter().zip(&shl).map(|(&x, &y)| x || y).collect();
+                    check(&or, &expected);
+                }
+            }
+        }
+        assert!(BitSet::new(0).is_empty());
+        assert_eq!(BitSet::new(0).first_set(), None);
+    }
+
+    #[test]
+    fn test_bitset_ops_against_vec() {
+        let mut rng = XorShift::new(123456789);
+        for n in [1, 64, 100] {
+            let x: Vec<bool> = (0..n).map(|_| rng.next(2) == 0).collect();
+            let y: Vec<bool> = (0..n).map(|_| rng.next(2) == 0).collect();
+            let (bx, by) = (to_bitset(&x), to_bitset(&y));
+            check(&(&bx & &by), &x.iter().zip(&y).map(|(&a, &b)| a && b).collect::<Vec<_>>());
+            check(&(&bx | &by), &x.iter().zip(&y).map(|(&a, &b)| a || b).collect::<Vec<_>>());
+            check(&(&bx ^ &by), &x.iter().zip(&y).map(|(&a, &b)| a != b).collect::<Vec<_>>());
+            let mut flipped = bx.clone();
+            (0..n).for_each(|i| flipped.flip(i));
+            check(&flipped, &x.iter().map(|&a| !a).collect::<Vec<_>>());
+            flipped.clear();
+            check(&flipped, &vec![false; n]);
+        }
+    }
+}
@@ -727,6 +727,55 @@ pub fn unionfind_connectivity_check(uf: &mut UnionFind) {
     println!("0 and 4 connected: {}", uf.connected(0, 4));
 }
 
+// モノイド（結合的な二項演算と単位元の組）
+// FoldableQueue やセグメント木系の構造で演算を差し替えるのに使う
+pub trait Monoid {
+    type S: Clone;
+    // 単位元
+    fn identity() -> Self::S;
+    // 二項演算（結合法則を満たすこと）
+    fn op(a: &Self::S, b: &Self::S) -> Self::S;
+}
+
+// 和のモノイド（i64）
+pub struct AddMonoid;
+
+impl Monoid for AddMonoid {
+    type S = i64;
+    fn identity() -> i64 {
+        0
+    }
+    fn op(a: &i64, b: &i64) -> i64 {
+        a + b
+    }
+}
+
+// 最小値のモノイド（i64）
+pub struct MinMonoid;
+
+impl Monoid for MinMonoid {
+    type S = i64;
+    fn identity() -> i64 {
+        i64::MAX
+    }
+    fn op(a: &i64, b: &i64) -> i64 {
+        *a.min(b)
+    }
+}
+
+// 最大値のモノイド（i64）
+pub struct MaxMonoid;
+
+impl Monoid for MaxMonoid {
+    type S = i64;
+    fn identity() -> i64 {
+        i64::MIN
+    }
+    fn op(a: &i64, b: &i64) -> i64 {
+        *a.max(b)
+    }
+}
+
 // セグメント木（区間和クエリ）
 pub struct SegmentTree {
     tree: Vec<i64>,
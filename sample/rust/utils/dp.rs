@@ -0,0 +1,503 @@
+use std::collections::VecDeque;
+
+// 直線 y = a * x + b を i128 で評価する（比較時のオーバーフロー対策）
+fn eval_line(line: (i64, i64), x: i64) -> i128 {
+    line.0 as i128 * x as i128 + line.1 as i128
+}
+
+// Li Chao Tree のノード（子がない場合は usize::MAX）
+struct LiChaoNode {
+    line: Option<(i64, i64)>,
+    left: usize,
+    right: usize,
+}
+
+// Li Chao Tree（整数区間 [lo, hi) 上の直線・線分の最小値/最大値クエリ）
+// ノードは必要になったときだけ作成するため、座標が 10^18 程度でも使える（hi - lo が i64 に収まること）
+pub struct LiChaoTree {
+    nodes: Vec<LiChaoNode>,
+    lo: i64,
+    hi: i64,
+    is_max: bool,
+}
+
+impl LiChaoTree {
+    // 最小値を求める Li Chao Tree を作成
+    pub fn new_min(lo: i64, hi: i64) -> Self {
+        Self::new(lo, hi, false)
+    }
+
+    // 最大値を求める Li Chao Tree を作成
+    pub fn new_max(lo: i64, hi: i64) -> Self {
+        Self::new(lo, hi, true)
+    }
+
+    fn new(lo: i64, hi: i64, is_max: bool) -> Self {
+        assert!(lo < hi);
+        assert!(hi.checked_sub(lo).is_some(), "hi - lo must fit in i64");
+        LiChaoTree {
+            nodes: vec![LiChaoNode { line: None, left: usize::MAX, right: usize::MAX }],
+            lo,
+            hi,
+            is_max,
+        }
+    }
+
+    // 最大値版は符号を反転した直線を最小値として管理する
+    fn normalize(&self, a: i64, b: i64) -> (i64, i64) {
+        if self.is_max {
+            (-a, -b)
+        } else {
+            (a, b)
+        }
+    }
+
+    fn new_node(&mut self) -> usize {
+        self.nodes.push(LiChaoNode { line: None, left: usize::MAX, right: usize::MAX });
+        self.nodes.len() - 1
+    }
+
+    // 直線 y = a * x + b を定義域全体に追加（O(log(hi - lo))）
+    pub fn add_line(&mut self, a: i64, b: i64) {
+        let line = self.normalize(a, b);
+        self.insert(0, self.lo, self.hi, line);
+    }
+
+    // 線分 y = a * x + b (l <= x < r) を追加（O(log^2(hi - lo))）
+    pub fn add_segment(&mut self, a: i64, b: i64, l: i64, r: i64) {
+        let l = l.max(self.lo);
+        let r = r.min(self.hi);
+        if l >= r {
+            return;
+        }
+        let line = self.normalize(a, b);
+        self.insert_segment(0, self.lo, self.hi, l, r, line);
+    }
+
+    fn insert_segment(&mut self, node: usize, nl: i64, nr: i64, l: i64, r: i64, line: (i64, i64)) {
+        if r <= nl || nr <= l {
+            return;
+        }
+        if l <= nl && nr <= r {
+            self.insert(node, nl, nr, line);
+            return;
+        }
+        let mid = nl + (nr - nl) / 2;
+        if self.nodes[node].left == usize::MAX {
+            let child = self.new_node();
+            self.nodes[node].left = child;
+        }
+        if self.nodes[node].right == usize::MAX {
+            let child = self.new_node();
+            self.nodes[node].right = child;
+        }
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.insert_segment(left, nl, mid, l, r, line);
+        self.insert_segment(right, mid, nr, l, r, line);
+    }
+
+    // ノード区間 [l, r) に直線を挿入（優劣が入れ替わる側の子へ降りていく）
+    fn insert(&mut self, mut node: usize, mut l: i64, mut r: i64, mut line: (i64, i64)) {
+        loop {
+            let cur = match self.nodes[node].line {
+                Some(cur) => cur,
+                None => {
+                    self.nodes[node].line = Some(line);
+                    return;
+                }
+            };
+            let mid = l + (r - l) / 2;
+            let left_better = eval_line(line, l) < eval_line(cur, l);
+            let mid_better = eval_line(line, mid) < eval_line(cur, mid);
+            if mid_better {
+                self.nodes[node].line = Some(line);
+                line = cur;
+            }
+            if r - l == 1 {
+                return;
+            }
+            if left_better != mid_better {
+                if self.nodes[node].left == usize::MAX {
+                    let child = self.new_node();
+                    self.nodes[node].left = child;
+                }
+                node = self.nodes[node].left;
+                r = mid;
+            } else {
+                if self.nodes[node].right == usize::MAX {
+                    let child = self.new_node();
+                    self.nodes[node].right = child;
+                }
+                node = self.nodes[node].right;
+                l = mid;
+            }
+        }
+    }
+
+    // x における最小値（最大値版では最大値）を取得、直線がなければ None
+    // 値は i64 に収まらないこともあるので i128 で返す
+    pub fn query(&self, x: i64) -> Option<i128> {
+        assert!(self.lo <= x && x < self.hi);
+        let (mut node, mut l, mut r) = (0, self.lo, self.hi);
+        let mut best: Option<i128> = None;
+        while node != usize::MAX {
+            if let Some(line) = self.nodes[node].line {
+                let y = eval_line(line, x);
+                best = Some(best.map_or(y, |b| b.min(y)));
+            }
+            let mid = l + (r - l) / 2;
+            if x < mid {
+                node = self.nodes[node].left;
+                r = mid;
+            } else {
+                node = self.nodes[node].right;
+                l = mid;
+            }
+        }
+        best.map(|y| if self.is_max { -y } else { y })
+    }
+}
+
+// Convex Hull Trick（傾きが単調に追加される場合の両端キュー版）
+// 最小値版は傾きを非増加順に、最大値版は傾きを非減少順に追加する
+pub struct ConvexHullTrick {
+    lines: VecDeque<(i64, i64)>,
+    is_max: bool,
+}
+
+impl ConvexHullTrick {
+    // 最小値を求める CHT を作成
+    pub fn new_min() -> Self {
+        ConvexHullTrick { lines: VecDeque::new(), is_max: false }
+    }
+
+    // 最大値を求める CHT を作成
+    pub fn new_max() -> Self {
+        ConvexHullTrick { lines: VecDeque::new(), is_max: true }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    // l2 が l1 と l3 に挟まれて不要になるか（傾きは a1 > a2 > a3）
+    fn is_redundant(l1: (i64, i64), l2: (i64, i64), l3: (i64, i64)) -> bool {
+        let lhs = (l2.1 as i128 - l1.1 as i128) * (l2.0 as i128 - l3.0 as i128);
+        let rhs = (l3.1 as i128 - l2.1 as i128) * (l1.0 as i128 - l2.0 as i128);
+        lhs >= rhs
+    }
+
+    // 直線 y = a * x + b を追加（償却 O(1)）
+    pub fn add_line(&mut self, a: i64, b: i64) {
+        let line = if self.is_max { (-a, -b) } else { (a, b) };
+        if let Some(&last) = self.lines.back() {
+            assert!(line.0 <= last.0, "slopes must be added monotonically");
+            if line.0 == last.0 {
+                if line.1 >= last.1 {
+                    return;
+                }
+                self.lines.pop_back();
+            }
+        }
+        while self.lines.len() >= 2 {
+            let n = self.lines.len();
+            if Self::is_redundant(self.lines[n - 2], self.lines[n - 1], line) {
+                self.lines.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.lines.push_back(line);
+    }
+
+    fn output(&self, y: i128) -> i128 {
+        if self.is_max {
+            -y
+        } else {
+            y
+        }
+    }
+
+    // 任意の x における最小値（最大値）を二分探索で取得（O(log n)、値は i128）
+    pub fn query(&self, x: i64) -> i128 {
+        assert!(!self.lines.is_empty());
+        let (mut lo, mut hi) = (0, self.lines.len() - 1);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if eval_line(self.lines[mid], x) <= eval_line(self.lines[mid + 1], x) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        self.output(eval_line(self.lines[lo], x))
+    }
+
+    // x が非減少順に与えられる場合のクエリ（償却 O(1)、先頭の不要な直線を捨てる）
+    pub fn query_monotone(&mut self, x: i64) -> i128 {
+        assert!(!self.lines.is_empty());
+        while self.lines.len() >= 2 && eval_line(self.lines[0], x) >= eval_line(self.lines[1], x) {
+            self.lines.pop_front();
+        }
+        self.output(eval_line(self.lines[0], x))
+    }
+}
+
+// Monotone Minima（各行の最小値の列番号が単調非減少な h x w 行列の行ごとの argmin）
+// Monge 行列はこの条件を満たす、O((h + w) log h)
+pub fn monotone_minima<T, F>(h: usize, w: usize, f: F) -> Vec<usize>
+where
+    T: PartialOrd,
+    F: Fn(usize, usize) -> T,
+{
+    let mut argmin = vec![0; h];
+    if h == 0 || w == 0 {
+        return argmin;
+    }
+    // (行区間 [top, bottom), 列区間 [left, right]) をスタックで処理
+    let mut stack = vec![(0, h, 0, w - 1)];
+    while let Some((top, bottom, left, right)) = stack.pop() {
+        if top >= bottom {
+            continue;
+        }
+        let mid = (top + bottom) / 2;
+        let mut best = left;
+        let mut best_val = f(mid, left);
+        for j in left + 1..=right {
+            let val = f(mid, j);
+            if val < best_val {
+                best = j;
+                best_val = val;
+            }
+        }
+        argmin[mid] = best;
+        stack.push((top, mid, left, best));
+        stack.push((mid + 1, bottom, best, right));
+    }
+    argmin
+}
+
+// 分割統治 DP の 1 段分: next[i] = min_{0 <= j < i} (prev[j] + cost(j, i))
+// 最適な j が i について単調非減少であること（cost が Monge など）が条件、O(n log n)
+// prev[j] が到達不能なら i64::MAX を入れておく、next[0] は i64::MAX
+pub fn divide_and_conquer_dp<F>(prev: &[i64], cost: F) -> Vec<i64>
+where
+    F: Fn(usize, usize) -> i64,
+{
+    let n = prev.len();
+    let mut next = vec![i64::MAX; n];
+    if n <= 1 {
+        return next;
+    }
+    // (i の区間 [l, r), j の探索範囲 [opt_l, opt_r])
+    let mut stack = vec![(1, n, 0, n - 2)];
+    while let Some((l, r, opt_l, opt_r)) = stack.pop() {
+        if l >= r {
+            continue;
+        }
+        let mid = (l + r) / 2;
+        let mut best = opt_l;
+        let mut best_val = i64::MAX;
+        for (j, &p) in prev.iter().enumerate().take(opt_r.min(mid - 1) + 1).skip(opt_l) {
+            if p == i64::MAX {
+                continue;
+            }
+            let val = p.saturating_add(cost(j, mid));
+            if val < best_val {
+                best = j;
+                best_val = val;
+            }
+        }
+        next[mid] = best_val;
+        stack.push((l, mid, opt_l, best));
+        stack.push((mid + 1, r, best, opt_r));
+    }
+    next
+}
+
+// Knuth-Yao 高速化による区間 DP
+// dp[i][j] = min_{i < k < j} (dp[i][k] + dp[k][j]) + w(i, j), dp[i][i + 1] = 0
+// w が四角不等式と単調性を満たすとき O(n^2)、返り値は (n + 1) x (n + 1) の dp 表
+pub fn knuth_interval_dp<F>(n: usize, w: F) -> Vec<Vec<i64>>
+where
+    F: Fn(usize, usize) -> i64,
+{
+    let mut dp = vec![vec![0i64; n + 1]; n + 1];
+    let mut opt = vec![vec![0usize; n + 1]; n + 1];
+    for i in 0..n {
+        opt[i][i + 1] = i + 1;
+    }
+    for len in 2..=n {
+        for i in 0..=n - len {
+            let j = i + len;
+            let mut best = i64::MAX;
+            let mut best_k = opt[i][j - 1];
+            let (lo, hi) = (opt[i][j - 1], opt[i + 1][j].min(j - 1));
+            for (k, &left) in dp[i].iter().enumerate().take(hi + 1).skip(lo) {
+                let val = left + dp[k][j];
+                if val < best {
+                    best = val;
+                    best_k = k;
+                }
+            }
+            dp[i][j] = best + w(i, j);
+            opt[i][j] = best_k;
+        }
+    }
+    dp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+
+    #[test]
+    fn test_li_chao_tree_lines_and_segments() {
+        let mut rng = XorShift::default();
+        let (lo, hi) = (-50, 50);
+        let mut min_tree = LiChaoTree::new_min(lo, hi);
+        let mut max_tree = LiChaoTree::new_max(lo, hi);
+        let mut segments = Vec::new();
+        for _ in 0..200 {
+            let a = rng.range(-100, 100);
+            let b = rng.range(-1000, 1000);
+            let l = rng.range(lo - 10, hi);
+            let r = rng.range(l + 1, hi + 10);
+            min_tree.add_segment(a, b, l, r);
+            max_tree.add_segment(a, b, l, r);
+            segments.push((a, b, l, r));
+            for x in lo..hi {
+                let values = segments.iter().filter(|s| s.2 <= x && x < s.3).map(|s| (s.0 * x + s.1) as i128);
+                assert_eq!(min_tree.query(x), values.clone().min());
+                assert_eq!(max_tree.query(x), values.max());
+            }
+        }
+    }
+
+    #[test]
+    fn test_li_chao_tree_huge_domain() {
+        let mut tree = LiChaoTree::new_min(-1_000_000_000_000_000_000, 1_000_000_000_000_000_000);
+        tree.add_line(1_000_000_000, 0);
+        tree.add_line(-1_000_000_000, 0);
+        tree.add_line(0, 5);
+        assert_eq!(tree.query(0), Some(0));
+        assert_eq!(tree.query(1_000_000_000), Some(-1_000_000_000_000_000_000));
+
+        // 値が i64 に収まらない場合
+        let mut tree = LiChaoTree::new_max(0, 1_000_000_000_000_000_000);
+        tree.add_line(1_000_000, 0);
+        assert_eq!(tree.query(999_999_999_999_999_999), Some(999_999_999_999_999_999 * 1_000_000));
+        // 幅が i64 に収まる最大の定義域
+        let mut tree = LiChaoTree::new_min(i64::MIN, -1);
+        tree.add_line(1, 0);
+        assert_eq!(tree.query(i64::MIN), Some(i64::MIN as i128));
+        assert_eq!(tree.query(-2), Some(-2));
+
+        let mut cht = ConvexHullTrick::new_min();
+        cht.add_line(-1_000_000_000_000, 0);
+        assert_eq!(cht.query(1_000_000_000_000), -1_000_000_000_000_000_000_000_000);
+    }
+
+    #[test]
+    fn test_convex_hull_trick() {
+        let mut rng = XorShift::new(2463534242);
+        let mut slopes: Vec<i64> = (0..100).map(|_| rng.range(-1000, 1000)).collect();
+        slopes.sort_unstable_by(|a, b| b.cmp(a));
+        let lines: Vec<(i64, i64)> = slopes.iter().map(|&a| (a, rng.range(-10000, 10000))).collect();
+
+        let mut cht = ConvexHullTrick::new_min();
+        let mut monotone = ConvexHullTrick::new_min();
+        for &(a, b) in &lines {
+            cht.add_line(a, b);
+            monotone.add_line(a, b);
+        }
+        for x in -100..100 {
+            let expected = lines.iter().map(|&(a, b)| (a * x + b) as i128).min().unwrap();
+            assert_eq!(cht.query(x), expected);
+            assert_eq!(monotone.query_monotone(x), expected);
+        }
+
+        let mut max_cht = ConvexHullTrick::new_max();
+        for &(a, b) in lines.iter().rev() {
+            max_cht.add_line(a, b);
+        }
+        for x in -100..100 {
+            let expected = lines.iter().map(|&(a, b)| (a * x + b) as i128).max().unwrap();
+            assert_eq!(max_cht.query(x), expected);
+        }
+    }
+
+    #[test]
+    fn test_monotone_minima() {
+        // (i - j)^2 + j は Monge 行列
+        let f = |i: usize, j: usize| (i as i64 - j as i64).pow(2) + j as i64;
+        let argmin = monotone_minima(30, 20, f);
+        for (i, &j) in argmin.iter().enumerate() {
+            let min = (0..20).map(|k| f(i, k)).min().unwrap();
+            assert_eq!(f(i, j), min);
+        }
+    }
+
+    #[test]
+    fn test_divide_and_conquer_dp() {
+        // 数列を k 個の区間に分割し、各区間の (区間和)^2 の総和を最小化する
+        let mut rng = XorShift::new(123456789);
+        let a: Vec<i64> = (0..40).map(|_| rng.range(1, 100)).collect();
+        let n = a.len();
+        let mut prefix = vec![0; n + 1];
+        for i in 0..n {
+            prefix[i + 1] = prefix[i] + a[i];
+        }
+        let cost = |j: usize, i: usize| (prefix[i] - prefix[j]).pow(2);
+
+        let mut fast = vec![i64::MAX; n + 1];
+        fast[0] = 0;
+        let mut naive = fast.clone();
+        for _ in 0..5 {
+            fast = divide_and_conquer_dp(&fast, cost);
+            let mut next = vec![i64::MAX; n + 1];
+            for (i, best) in next.iter_mut().enumerate().skip(1) {
+                for (j, &d) in naive.iter().enumerate().take(i) {
+                    if d != i64::MAX {
+                        *best = (*best).min(d + cost(j, i));
+                    }
+                }
+            }
+            naive = next;
+            assert_eq!(fast, naive);
+        }
+    }
+
+    #[test]
+    fn test_knuth_interval_dp() {
+        // スライムの合体（隣接する区間をまとめるコストは区間和）
+        let a = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let n = a.len();
+        let mut prefix = vec![0; n + 1];
+        for i in 0..n {
+            prefix[i + 1] = prefix[i] + a[i];
+        }
+        let w = |i: usize, j: usize| prefix[j] - prefix[i];
+        let dp = knuth_interval_dp(n, w);
+
+        let mut naive = vec![vec![0i64; n + 1]; n + 1];
+        for len in 2..=n {
+            for i in 0..=n - len {
+                let j = i + len;
+                naive[i][j] = (i + 1..j).map(|k| naive[i][k] + naive[k][j]).min().unwrap() + w(i, j);
+            }
+        }
+        assert_eq!(dp, naive);
+    }
+
+    #[test]
+    #[should_panic(expected = "hi - lo must fit in i64")]
+    fn test_li_chao_tree_domain_too_wide() {
+        LiChaoTree::new_max(i64::MIN, i64::MAX);
+    }
+}
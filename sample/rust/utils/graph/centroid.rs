@@ -0,0 +1,207 @@
+use super::Adjacency;
+use std::collections::VecDeque;
+
+// 重心分解
+// 木の重心を取り除いて残りの部分木をそれぞれ再帰的に分解したときの、重心どうしの木（重心木）を持つ
+// 重心木の深さは O(log n) で、各頂点はたかだか O(log n) 個の重心の担当する部分木に含まれる
+pub struct CentroidDecomposition {
+    // 重心木での親（最初の重心なら None）
+    pub parent: Vec<Option<usize>>,
+    // 重心木での深さ（最初の重心が 0）
+    pub depth: Vec<usize>,
+    // 重心を見つけた順（重心木の BFS 順）
+    order: Vec<usize>,
+}
+
+// start を含み、blocked を満たさない頂点だけからなる連結成分を BFS 順で求める
+// 2 つ目の返り値は BFS 木で各頂点に入る辺（親, 親の隣接リストでの位置）
+fn component<G: Adjacency>(g: &G, start: usize, blocked: impl Fn(usize) -> bool) -> (Vec<usize>, Vec<(usize, usize)>) {
+    let mut order = vec![start];
+    let mut via = vec![(usize::MAX, 0)];
+    let mut head = 0;
+    while head < order.len() {
+        let (u, p) = (order[head], via[head].0);
+        head += 1;
+        for (i, e) in g.neighbors(u).iter().enumerate() {
+            if e.to != p && !blocked(e.to) {
+                order.push(e.to);
+                via.push((u, i));
+            }
+        }
+    }
+    (order, via)
+}
+
+impl CentroidDecomposition {
+    // 無向グラフとして与えられた木（森でもよい）を分解する（O(n log n)、再帰を使わない）
+    pub fn new<G: Adjacency>(g: &G) -> Self {
+        let n = g.vertex_count();
+        let mut parent = vec![None; n];
+        let mut depth = vec![usize::MAX; n];
+        let mut order = Vec::with_capacity(n);
+        let mut size = vec![0; n];
+        let mut local = vec![0; n];
+        // (まだ分解していない成分の頂点の 1 つ, 重心木での親)
+        let mut queue = VecDeque::new();
+        for root in 0..n {
+            if depth[root] != usize::MAX {
+                continue;
+            }
+            queue.push_back((root, None));
+            while let Some((start, centroid_parent)) = queue.pop_front() {
+                let (vertices, bfs_parent) = component(g, start, |v| depth[v] != usize::MAX);
+                for (i, &v) in vertices.iter().enumerate() {
+                    local[v] = i;
+                    size[v] = 1;
+                }
+                for i in (1..vertices.len()).rev() {
+                    size[bfs_parent[i].0] += size[vertices[i]];
+                }
+                // 根から、部分木が半分より大きい子の方へ進む
+                let total = vertices.len();
+                let mut c = start;
+                loop {
+                    let heavy = g.neighbors(c).iter().map(|e| e.to).find(|&to| {
+                        depth[to] == usize::MAX && bfs_parent[local[to]].0 == c && size[to] * 2 > total
+                    });
+                    match heavy {
+                        Some(to) => c = to,
+                        None => break,
+                    }
+                }
+                parent[c] = centroid_parent;
+                depth[c] = centroid_parent.map_or(0, |p| depth[p] + 1);
+                order.push(c);
+                for e in g.neighbors(c) {
+                    if depth[e.to] == usize::MAX {
+                        queue.push_back((e.to, Some(c)));
+                    }
+                }
+            }
+        }
+        CentroidDecomposition { parent, depth, order }
+    }
+
+    // 重心を見つけた順（親の重心は子の重心より先に現れる）
+    pub fn centroids(&self) -> &[usize] {
+        &self.order
+    }
+
+    // 重心 c が担当する部分木（c を取り除く直前の c を含む連結成分）の頂点（BFS 順で先頭は c）
+    pub fn subtree<G: Adjacency>(&self, g: &G, c: usize) -> Vec<usize> {
+        component(g, c, |v| self.depth[v] <= self.depth[c] && v != c).0
+    }
+
+    // 各重心について (重心, 担当する部分木の頂点) を順に返す（全体で O(n log n)）
+    pub fn visits<'a, G: Adjacency>(&'a self, g: &'a G) -> impl Iterator<Item = (usize, Vec<usize>)> + 'a {
+        self.order.iter().map(move |&c| (c, self.subtree(g, c)))
+    }
+}
+
+// 距離（辺の長さの和）が k 以下の頂点の組 {u, v}（u != v）の個数（O(n log^2 n)）
+// 辺の長さは weight で与える（非負であること、重みなしグラフなら |_| 1）
+pub fn count_pairs_within<G: Adjacency>(g: &G, k: i64, weight: impl Fn(&G::Weight) -> i64) -> u64 {
+    // 距離の列から、和が k 以下の組の個数
+    let count = |dists: &mut Vec<i64>| -> u64 {
+        dists.sort_unstable();
+        let mut result = 0;
+        let mut r = dists.len();
+        for l in 0..dists.len() {
+            while r > l + 1 && dists[l] + dists[r - 1] > k {
+                r -= 1;
+            }
+            if r <= l + 1 {
+                break;
+            }
+            result += (r - l - 1) as u64;
+        }
+        result
+    };
+
+    let cd = CentroidDecomposition::new(g);
+    let mut total = 0;
+    let mut dist = vec![0i64; g.vertex_count()];
+    for &c in cd.centroids() {
+        let blocked = |v: usize| cd.depth[v] <= cd.depth[c];
+        // c を端点に含む組は、c の距離 0 を全体に入れて数える
+        let mut all = vec![0];
+        for e in g.neighbors(c) {
+            if blocked(e.to) {
+                continue;
+            }
+            let (vertices, via) = component(g, e.to, blocked);
+            dist[e.to] = weight(&e.weight);
+            for i in 1..vertices.len() {
+                let (p, j) = via[i];
+                dist[vertices[i]] = dist[p] + weight(&g.neighbors(p)[j].weight);
+            }
+            let mut branch: Vec<i64> = vertices.iter().map(|&v| dist[v]).collect();
+            all.extend_from_slice(&branch);
+            // 同じ子の部分木どうしの組は c を通らないので引く
+            total -= count(&mut branch) as i64;
+        }
+        total += count(&mut all) as i64;
+    }
+    total as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Graph;
+    use super::*;
+    use super::super::super::test_util::{tree_distances, XorShift};
+
+    #[test]
+    fn test_centroid_tree() {
+        let mut rng = XorShift::default();
+        for n in [1, 2, 3, 10, 100, 1000] {
+            let mut g = Graph::new_undirected(n);
+            for i in 1..n {
+                let p = if rng.next(3) == 0 { rng.next(i) } else { i - 1 };
+                g.add_edge(p, i, 1i64);
+            }
+            let cd = CentroidDecomposition::new(&g);
+            let mut seen = vec![false; n];
+            let mut subtree_size = vec![0; n];
+            for (c, vertices) in cd.visits(&g) {
+                assert_eq!(vertices[0], c);
+                assert!(!seen[c]);
+                seen[c] = true;
+                subtree_size[c] = vertices.len();
+                if let Some(p) = cd.parent[c] {
+                    assert!(seen[p]);
+                    assert_eq!(cd.depth[c], cd.depth[p] + 1);
+                    // 重心で分けると部分木の大きさは半分以下になる
+                    assert!(vertices.len() * 2 <= subtree_size[p]);
+                } else {
+                    assert_eq!(vertices.len(), n);
+                }
+                assert!(vertices.iter().all(|&v| v == c || cd.depth[v] > cd.depth[c]));
+            }
+            assert!(seen.iter().all(|&x| x));
+            assert!(cd.depth.iter().all(|&d| 1 << d <= n));
+        }
+    }
+
+    #[test]
+    fn test_count_pairs_within_against_brute_force() {
+        let mut rng = XorShift::new(2463534242);
+        for _ in 0..50 {
+            let n = 1 + rng.next(60);
+            let mut g = Graph::new_undirected(n);
+            // 森でもよい
+            for i in 1..n {
+                if rng.next(10) > 0 {
+                    g.add_edge(rng.next(i), i, rng.next(5) as i64);
+                }
+            }
+            let all: Vec<Vec<Option<(i64, u64)>>> = (0..n).map(|s| tree_distances(&g, s)).collect();
+            for k in [0, 1, 3, 7, 20, 1000] {
+                let expected = (0..n).flat_map(|u| (u + 1..n).map(move |v| (u, v))).filter(|&(u, v)| all[u][v].is_some_and(|d| d.0 <= k)).count();
+                assert_eq!(count_pairs_within(&g, k, |&w| w), expected as u64);
+            }
+        }
+        let g = Graph::from_edges(4, &[(0, 1, ()), (1, 2, ()), (2, 3, ())], false);
+        assert_eq!(count_pairs_within(&g, 2, |_| 1), 5);
+    }
+}
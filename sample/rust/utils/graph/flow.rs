@@ -0,0 +1,232 @@
+use std::collections::VecDeque;
+
+// 残余グラフの辺（rev は逆辺の g[to] での位置）
+#[derive(Clone, Debug)]
+struct ResidualEdge {
+    to: usize,
+    rev: usize,
+    cap: i64,
+}
+
+// 追加した辺の状態
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlowEdge {
+    pub from: usize,
+    pub to: usize,
+    pub cap: i64,
+    pub flow: i64,
+}
+
+// 最大流（Dinic 法、O(n^2 m)、容量は i64）
+// 割り当て問題や燃やす埋める（最小カット）に使う
+pub struct MaxFlow {
+    g: Vec<Vec<ResidualEdge>>,
+    // 辺番号から (from, g[from] での位置)
+    pos: Vec<(usize, usize)>,
+}
+
+impl MaxFlow {
+    pub fn new(n: usize) -> Self {
+        MaxFlow { g: vec![Vec::new(); n], pos: Vec::new() }
+    }
+
+    // 容量 cap の辺 from -> to を追加して辺番号を返す
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: i64) -> usize {
+        let n = self.g.len();
+        assert!(from < n && to < n, "edge ({}, {}) out of range for {} vertices", from, to, n);
+        assert!(cap >= 0, "capacity must be non-negative");
+        let id = self.pos.len();
+        let from_pos = self.g[from].len();
+        // 自己ループでは逆辺が同じリストの次の位置に入る
+        let to_pos = self.g[to].len() + (from == to) as usize;
+        self.pos.push((from, from_pos));
+        self.g[from].push(ResidualEdge { to, rev: to_pos, cap });
+        self.g[to].push(ResidualEdge { to: from, rev: from_pos, cap: 0 });
+        id
+    }
+
+    // 辺番号 id の辺の容量と現在の流量
+    pub fn get_edge(&self, id: usize) -> FlowEdge {
+        let (from, i) = self.pos[id];
+        let e = &self.g[from][i];
+        let back = &self.g[e.to][e.rev];
+        FlowEdge { from, to: e.to, cap: e.cap + back.cap, flow: back.cap }
+    }
+
+    // 追加した順のすべての辺
+    pub fn edges(&self) -> Vec<FlowEdge> {
+        (0..self.pos.len()).map(|id| self.get_edge(id)).collect()
+    }
+
+    // s から t へ流せるだけ流し、流した量を返す
+    pub fn flow(&mut self, s: usize, t: usize) -> i64 {
+        self.flow_with_limit(s, t, i64::MAX)
+    }
+
+    // s から t へ limit を上限として流し、流した量を返す（上限に達したら打ち切る）
+    // 続けて呼ぶと、それまでの流れに追加して流す
+    pub fn flow_with_limit(&mut self, s: usize, t: usize, limit: i64) -> i64 {
+        assert!(s != t, "source and sink must be different");
+        let n = self.g.len();
+        let mut total = 0;
+        while total < limit {
+            let level = self.levels(s);
+            if level[t] == usize::MAX {
+                break;
+            }
+            let mut iter = vec![0; n];
+            let f = self.blocking_flow(s, t, limit - total, &level, &mut iter);
+            if f == 0 {
+                break;
+            }
+            total += f;
+        }
+        total
+    }
+
+    // 残余グラフでの s からの BFS の距離（到達不能なら usize::MAX）
+    fn levels(&self, s: usize) -> Vec<usize> {
+        let mut level = vec![usize::MAX; self.g.len()];
+        level[s] = 0;
+        let mut queue = VecDeque::from(vec![s]);
+        while let Some(u) = queue.pop_front() {
+            for e in &self.g[u] {
+                if e.cap > 0 && level[e.to] == usize::MAX {
+                    level[e.to] = level[u] + 1;
+                    queue.push_back(e.to);
+                }
+            }
+        }
+        level
+    }
+
+    // レベルグラフ上で増加路を繰り返し探して流す（再帰を使わない）
+    fn blocking_flow(&mut self, s: usize, t: usize, limit: i64, level: &[usize], iter: &mut [usize]) -> i64 {
+        let mut total = 0;
+        // s から現在の頂点までの辺 (頂点, g[頂点] での位置)
+        let mut path: Vec<(usize, usize)> = Vec::new();
+        let mut v = s;
+        while total < limit {
+            if v == t {
+                let f = path.iter().map(|&(u, i)| self.g[u][i].cap).fold(limit - total, i64::min);
+                for &(u, i) in &path {
+                    let (to, rev) = (self.g[u][i].to, self.g[u][i].rev);
+                    self.g[u][i].cap -= f;
+                    self.g[to][rev].cap += f;
+                }
+                total += f;
+                // 飽和した最初の辺の手前まで戻る（上限に達した場合は飽和した辺がないこともある）
+                match path.iter().position(|&(u, i)| self.g[u][i].cap == 0) {
+                    Some(k) => {
+                        v = path[k].0;
+                        path.truncate(k);
+                    }
+                    None => break,
+                }
+                continue;
+            }
+            let edges = &self.g[v];
+            while iter[v] < edges.len() {
+                let e = &edges[iter[v]];
+                if e.cap > 0 && level[e.to] == level[v] + 1 {
+                    break;
+                }
+                iter[v] += 1;
+            }
+            if iter[v] < edges.len() {
+                path.push((v, iter[v]));
+                v = edges[iter[v]].to;
+            } else {
+                // 行き止まりなので 1 つ戻り、その辺は今後使わない
+                match path.pop() {
+                    Some((u, _)) => {
+                        iter[u] += 1;
+                        v = u;
+                    }
+                    None => break,
+                }
+            }
+        }
+        total
+    }
+
+    // 最小カットの s 側（残余グラフで s から到達できる頂点）
+    // flow(s, t) の後に呼ぶと、cut[u] && !cut[v] となる辺 u -> v の容量の和が最大流に等しい
+    pub fn min_cut(&self, s: usize) -> Vec<bool> {
+        self.levels(s).into_iter().map(|d| d != usize::MAX).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::super::test_util::XorShift;
+
+    #[test]
+    fn test_max_flow_against_brute_force_min_cut() {
+        let mut rng = XorShift::default();
+        for _ in 0..200 {
+            let n = 2 + rng.next(7);
+            let m = rng.next(20);
+            let edges: Vec<(usize, usize, i64)> = (0..m).map(|_| (rng.next(n), rng.next(n), rng.next(10) as i64)).collect();
+            let (s, t) = (0, n - 1);
+            let cut_capacity = |side: &[bool]| -> i64 { edges.iter().filter(|&&(u, v, _)| side[u] && !side[v]).map(|e| e.2).sum() };
+            let expected = (0..1usize << n)
+                .filter(|mask| mask >> s & 1 == 1 && mask >> t & 1 == 0)
+                .map(|mask| cut_capacity(&(0..n).map(|i| mask >> i & 1 == 1).collect::<Vec<_>>()))
+                .min()
+                .unwrap();
+
+            let mut mf = MaxFlow::new(n);
+            for &(u, v, c) in &edges {
+                mf.add_edge(u, v, c);
+            }
+            // 上限付きで途中まで流してから残りを流す
+            let limit = rng.next(10) as i64;
+            let first = mf.flow_with_limit(s, t, limit);
+            assert_eq!(first, limit.min(expected));
+            assert_eq!(first + mf.flow(s, t), expected);
+
+            // 容量制約と流量保存則
+            let mut balance = vec![0i64; n];
+            for (id, e) in mf.edges().into_iter().enumerate() {
+                assert_eq!((e.from, e.to, e.cap), edges[id]);
+                assert!(0 <= e.flow && e.flow <= e.cap);
+                balance[e.from] -= e.flow;
+                balance[e.to] += e.flow;
+            }
+            assert_eq!(balance[t], expected);
+            assert!((0..n).filter(|&v| v != s && v != t).all(|v| balance[v] == 0));
+
+            let cut = mf.min_cut(s);
+            assert!(cut[s] && !cut[t]);
+            assert_eq!(cut_capacity(&cut), expected);
+        }
+    }
+
+    #[test]
+    fn test_bipartite_matching() {
+        // 左 0..3、右 3..6、始点 6、終点 7
+        let pairs = [(0, 3), (0, 4), (1, 3), (2, 4), (2, 5)];
+        let mut mf = MaxFlow::new(8);
+        let ids: Vec<usize> = pairs.iter().map(|&(l, r)| mf.add_edge(l, r, 1)).collect();
+        for i in 0..3 {
+            mf.add_edge(6, i, 1);
+            mf.add_edge(3 + i, 7, 1);
+        }
+        assert_eq!(mf.flow(6, 7), 3);
+        let matched: Vec<(usize, usize)> = ids.iter().map(|&id| mf.get_edge(id)).filter(|e| e.flow == 1).map(|e| (e.from, e.to)).collect();
+        assert_eq!(matched.len(), 3);
+        assert_eq!(mf.flow(6, 7), 0);
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let n = 200_000;
+        let mut mf = MaxFlow::new(n);
+        for i in 1..n {
+            mf.add_edge(i - 1, i, 1_000_000_000_000 - i as i64);
+        }
+        assert_eq!(mf.flow(0, n - 1), 1_000_000_000_000 - (n - 1) as i64);
+    }
+}
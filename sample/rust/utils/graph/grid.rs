@@ -0,0 +1,335 @@
+use super::super::io::Scanner;
+use super::Graph;
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+
+// 上下左右の移動 (dr, dc)
+pub const DIR4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+// 斜めを含む 8 方向の移動 (dr, dc)
+pub const DIR8: [(isize, isize); 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+// h 行 w 列のグリッド（迷路などの 2 次元の盤面）
+// マス (r, c) は 1 次元の添字 r * w + c と相互に変換できる
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    h: usize,
+    w: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // すべてのマスが value の h x w のグリッド
+    pub fn new(h: usize, w: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid { h, w, cells: vec![value; h * w] }
+    }
+
+    // 行のリストから作成（各行の長さは等しいこと）
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let h = rows.len();
+        let w = if h > 0 { rows[0].len() } else { 0 };
+        assert!(rows.iter().all(|row| row.len() == w), "rows must have the same length");
+        Grid { h, w, cells: rows.into_iter().flatten().collect() }
+    }
+
+    pub fn h(&self) -> usize {
+        self.h
+    }
+
+    pub fn w(&self) -> usize {
+        self.w
+    }
+
+    // (r, c) を 1 次元の添字に変換する
+    pub fn to_index(&self, r: usize, c: usize) -> usize {
+        debug_assert!(r < self.h && c < self.w);
+        r * self.w + c
+    }
+
+    // 1 次元の添字を (r, c) に変換する
+    pub fn to_pos(&self, i: usize) -> (usize, usize) {
+        (i / self.w, i % self.w)
+    }
+
+    // (r, c) がグリッドの内側か（負の座標も受け付ける）
+    pub fn in_bounds(&self, r: isize, c: isize) -> bool {
+        0 <= r && r < self.h as isize && 0 <= c && c < self.w as isize
+    }
+
+    // (r, c) から dirs の方向に 1 歩進んだマスのうちグリッドの内側にあるもの
+    fn neighbors(&self, r: usize, c: usize, dirs: &'static [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> {
+        let (h, w) = (self.h as isize, self.w as isize);
+        dirs.iter().filter_map(move |&(dr, dc)| {
+            let (nr, nc) = (r as isize + dr, c as isize + dc);
+            if 0 <= nr && nr < h && 0 <= nc && nc < w {
+                Some((nr as usize, nc as usize))
+            } else {
+                None
+            }
+        })
+    }
+
+    // 上下左右に隣接するマス
+    pub fn neighbors4(&self, r: usize, c: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(r, c, &DIR4)
+    }
+
+    // 斜めを含めて隣接するマス
+    pub fn neighbors8(&self, r: usize, c: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(r, c, &DIR8)
+    }
+
+    // 第 r 行
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.w..(r + 1) * self.w]
+    }
+
+    // すべてのマスの ((r, c), 値)
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let w = self.w;
+        self.cells.iter().enumerate().map(move |(i, v)| ((i / w, i % w), v))
+    }
+
+    // 条件を満たすすべてのマス
+    pub fn positions(&self, f: impl Fn(&T) -> bool) -> Vec<(usize, usize)> {
+        self.iter().filter(|(_, v)| f(v)).map(|(p, _)| p).collect()
+    }
+
+    // 各マスに f を適用したグリッド
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid { h: self.h, w: self.w, cells: self.cells.iter().map(f).collect() }
+    }
+
+    // 転置（w x h のグリッドになる）
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.w, self.h, |r, c| (c, r))
+    }
+
+    // 時計回りに 90 度回転（w x h のグリッドになる）
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.w, self.h, |r, c| (self.h - 1 - c, r))
+    }
+
+    // 反時計回りに 90 度回転（w x h のグリッドになる）
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.w, self.h, |r, c| (c, self.w - 1 - r))
+    }
+
+    // 新しいグリッドの (r, c) に元のグリッドの from(r, c) を置く
+    fn remap(&self, h: usize, w: usize, from: impl Fn(usize, usize) -> (usize, usize)) -> Self
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(h * w);
+        for r in 0..h {
+            for c in 0..w {
+                let (sr, sc) = from(r, c);
+                cells.push(self[(sr, sc)].clone());
+            }
+        }
+        Grid { h, w, cells }
+    }
+
+    // 複数の始点からの BFS（passable を満たすマスだけを上下左右に移動する）
+    // 各マスへの最短の歩数を返す（到達不能なら None）
+    pub fn bfs(&self, sources: &[(usize, usize)], passable: impl Fn(&T) -> bool) -> Grid<Option<usize>> {
+        self.bfs_01(sources, |v| if passable(v) { Some(1) } else { None })
+    }
+
+    // 複数の始点からの 0-1 BFS
+    // cost(v) は値が v のマスに入るコスト（0 か 1、入れなければ None）
+    // 例: 壁を壊して進むなら、通路は Some(0)、壁は Some(1)
+    pub fn bfs_01(&self, sources: &[(usize, usize)], cost: impl Fn(&T) -> Option<usize>) -> Grid<Option<usize>> {
+        let mut dist = Grid::new(self.h, self.w, None);
+        let mut deque = VecDeque::new();
+        for &(r, c) in sources {
+            dist[(r, c)] = Some(0);
+            deque.push_back((0, r, c));
+        }
+        while let Some((d, r, c)) = deque.pop_front() {
+            if dist[(r, c)] != Some(d) {
+                continue;
+            }
+            for (nr, nc) in self.neighbors4(r, c) {
+                let Some(w) = cost(&self[(nr, nc)]) else { continue };
+                assert!(w <= 1, "0-1 BFS requires costs 0 or 1");
+                let nd = d + w;
+                if dist[(nr, nc)].is_none_or(|old| nd < old) {
+                    dist[(nr, nc)] = Some(nd);
+                    if w == 0 {
+                        deque.push_front((nd, nr, nc));
+                    } else {
+                        deque.push_back((nd, nr, nc));
+                    }
+                }
+            }
+        }
+        dist
+    }
+
+    // passable を満たすマスを上下左右のつながりで連結成分に分ける
+    // 返り値は各マスの成分番号（passable でないマスは None）と成分の個数
+    pub fn label_components(&self, passable: impl Fn(&T) -> bool) -> (Grid<Option<usize>>, usize) {
+        let mut label = Grid::new(self.h, self.w, None);
+        let mut count = 0;
+        let mut stack = Vec::new();
+        for start in 0..self.cells.len() {
+            if label.cells[start].is_some() || !passable(&self.cells[start]) {
+                continue;
+            }
+            label.cells[start] = Some(count);
+            stack.push(self.to_pos(start));
+            while let Some((r, c)) = stack.pop() {
+                for (nr, nc) in self.neighbors4(r, c) {
+                    if label[(nr, nc)].is_none() && passable(&self[(nr, nc)]) {
+                        label[(nr, nc)] = Some(count);
+                        stack.push((nr, nc));
+                    }
+                }
+            }
+            count += 1;
+        }
+        (label, count)
+    }
+
+    // passable を満たすマスを頂点（添字は to_index）、上下左右の隣接を辺とする無向グラフ
+    pub fn to_graph(&self, passable: impl Fn(&T) -> bool) -> Graph<()> {
+        let mut g = Graph::new_undirected(self.h * self.w);
+        for r in 0..self.h {
+            for c in 0..self.w {
+                if !passable(&self[(r, c)]) {
+                    continue;
+                }
+                // 右と下への辺だけを追加する
+                for (nr, nc) in [(r, c + 1), (r + 1, c)] {
+                    if nr < self.h && nc < self.w && passable(&self[(nr, nc)]) {
+                        g.add_edge(self.to_index(r, c), self.to_index(nr, nc), ());
+                    }
+                }
+            }
+        }
+        g
+    }
+}
+
+impl Grid<char> {
+    // h 行の文字列として与えられるグリッドを読み取る
+    pub fn read<R: BufRead>(sc: &mut Scanner<R>, h: usize) -> Self {
+        Grid::from_rows((0..h).map(|_| sc.read_chars()).collect())
+    }
+
+    // 文字 ch のマスのうち最初のもの
+    pub fn find(&self, ch: char) -> Option<(usize, usize)> {
+        self.cells.iter().position(|&x| x == ch).map(|i| self.to_pos(i))
+    }
+}
+
+// walls に含まれない文字のマスを通れるとする判定（bfs などに渡す）
+// 例: grid.bfs(&[start], passable_except(&['#']))
+pub fn passable_except(walls: &[char]) -> impl Fn(&char) -> bool + '_ {
+    move |ch| !walls.contains(ch)
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        assert!(r < self.h && c < self.w, "({}, {}) out of range for {}x{} grid", r, c, self.h, self.w);
+        &self.cells[r * self.w + c]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+        assert!(r < self.h && c < self.w, "({}, {}) out of range for {}x{} grid", r, c, self.h, self.w);
+        &mut self.cells[r * self.w + c]
+    }
+}
+
+impl std::fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for r in 0..self.h {
+            let line: String = self.row(r).iter().collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::shortest_path::bfs;
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        Grid::from_rows(rows.iter().map(|s| s.chars().collect()).collect())
+    }
+
+    #[test]
+    fn test_grid_bfs() {
+        let input = "4 5\nS.#..\n.##.#\n...#G\n#.#..\n";
+        let mut sc = Scanner::new(input.as_bytes());
+        let (h, _w): (usize, usize) = (sc.read(), sc.read());
+        let maze = Grid::read(&mut sc, h);
+        let (s, t) = (maze.find('S').unwrap(), maze.find('G').unwrap());
+        assert_eq!((s, t), ((0, 0), (2, 4)));
+
+        let dist = maze.bfs(&[s], passable_except(&['#']));
+        assert_eq!(dist[(2, 2)], Some(4));
+        assert_eq!(dist[(0, 3)], None);
+        assert_eq!(dist[t], None);
+        // グリッドをグラフにした BFS と一致する
+        let g = maze.to_graph(passable_except(&['#']));
+        let sp = bfs(&g, maze.to_index(s.0, s.1));
+        for ((r, c), &d) in dist.iter() {
+            assert_eq!(d, sp.dist[maze.to_index(r, c)].map(|x| x as usize));
+        }
+
+        // 壁を壊すコストが 1 の 0-1 BFS
+        let broken = maze.bfs_01(&[s], |&ch| Some(if ch == '#' { 1 } else { 0 }));
+        assert_eq!(broken[t], Some(1));
+        assert_eq!(broken[(1, 2)], Some(1));
+
+        // 複数始点
+        let multi = maze.bfs(&[s, t], |&ch| ch != '#');
+        assert_eq!(multi[(3, 4)], Some(1));
+        assert_eq!(multi[(0, 4)], None);
+        assert_eq!(multi[(3, 1)], Some(4));
+    }
+
+    #[test]
+    fn test_grid_transform_and_components() {
+        let g = grid(&["ab#", "#cd"]);
+        assert_eq!(g.neighbors4(0, 0).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(g.neighbors8(1, 2).count(), 3);
+        assert_eq!(g.to_pos(g.to_index(1, 2)), (1, 2));
+        assert!(g.in_bounds(1, 2) && !g.in_bounds(-1, 0) && !g.in_bounds(0, 3));
+        assert_eq!(g.rotate_cw(), grid(&["#a", "cb", "d#"]));
+        assert_eq!(g.rotate_ccw(), grid(&["#d", "bc", "a#"]));
+        assert_eq!(g.transpose(), grid(&["a#", "bc", "#d"]));
+        assert_eq!(g.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), g);
+        assert_eq!(g.rotate_cw().to_string(), "#a\ncb\nd#\n");
+
+        let g = grid(&["..#..", "#.#.#", "..#..", "####.", ".#..."]);
+        let (label, count) = g.label_components(passable_except(&['#']));
+        assert_eq!(count, 3);
+        assert_eq!(label[(0, 0)], label[(2, 1)]);
+        assert_eq!(label[(0, 3)], label[(4, 2)]);
+        assert_ne!(label[(0, 0)], label[(0, 3)]);
+        assert_eq!(label[(4, 0)], Some(2));
+        assert_eq!(label[(0, 2)], None);
+        assert_eq!(g.positions(|&ch| ch == '.').len(), 15);
+    }
+}
@@ -0,0 +1,214 @@
+use super::lca::bfs_tree;
+use super::Adjacency;
+
+// パスを分けた位置の区間 [l, r) のリスト（上り, 下り）
+pub type PathRanges = (Vec<(usize, usize)>, Vec<(usize, usize)>);
+
+// 重軽分解（HLD）
+// 木の頂点を、パスが O(log n) 個の連続区間に、部分木が 1 個の連続区間になるように並べる
+// 頂点 v の値を列の pos(v) 番目に置けば、パスや部分木のクエリをセグメント木などの区間クエリにできる
+pub struct Hld {
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+    // 頂点が属する heavy path の最も浅い頂点
+    head: Vec<usize>,
+    pos: Vec<usize>,
+    size: Vec<usize>,
+    // order[i] は pos が i の頂点
+    order: Vec<usize>,
+}
+
+impl Hld {
+    // 無向グラフとして与えられた木を root を根として分解する（O(n)、再帰を使わない）
+    pub fn new<G: Adjacency>(g: &G, root: usize) -> Self {
+        let (bfs_order, parent, depth) = bfs_tree(g, root);
+        let n = parent.len();
+        let mut size = vec![1; n];
+        for &v in bfs_order.iter().skip(1).rev() {
+            size[parent[v]] += size[v];
+        }
+        // 部分木が最も大きい子（heavy child）
+        let mut heavy = vec![usize::MAX; n];
+        for &v in bfs_order.iter().skip(1) {
+            let p = parent[v];
+            if heavy[p] == usize::MAX || size[heavy[p]] < size[v] {
+                heavy[p] = v;
+            }
+        }
+
+        // heavy child を最初にたどる DFS の行きがけ順に並べる
+        let mut head = vec![root; n];
+        let mut pos = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            pos[v] = order.len();
+            order.push(v);
+            for e in g.neighbors(v) {
+                if e.to != root && parent[e.to] == v && e.to != heavy[v] {
+                    head[e.to] = e.to;
+                    stack.push(e.to);
+                }
+            }
+            if heavy[v] != usize::MAX {
+                head[heavy[v]] = head[v];
+                stack.push(heavy[v]);
+            }
+        }
+        Hld { parent, depth, head, pos, size, order }
+    }
+
+    // 頂点 v の列での位置
+    pub fn pos(&self, v: usize) -> usize {
+        self.pos[v]
+    }
+
+    // 列の i 番目の頂点
+    pub fn vertex_at(&self, i: usize) -> usize {
+        self.order[i]
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    // v の部分木の位置の区間 [l, r)
+    pub fn subtree_range(&self, v: usize) -> (usize, usize) {
+        (self.pos[v], self.pos[v] + self.size[v])
+    }
+
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] > self.depth[self.head[v]] {
+                u = self.parent[self.head[u]];
+            } else {
+                v = self.parent[self.head[v]];
+            }
+        }
+        if self.depth[u] < self.depth[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    // u と v の間の辺の本数
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    // u から v へのパスの頂点を位置の区間 [l, r) に分けて返す
+    // 返り値は (上り, 下り) で、パスは次の順に頂点をたどる
+    // - 上り: u から lca まで。各区間は位置の大きい方から小さい方へたどる
+    // - 下り: lca の次から v まで。各区間は位置の小さい方から大きい方へたどる
+    // 非可換なモノイドでは、上りの区間は逆順の積を使う
+    pub fn path_ranges(&self, u: usize, v: usize) -> PathRanges {
+        self.ranges(u, v, false)
+    }
+
+    // path_ranges と同じだが lca を含めない（辺の値を子の側の頂点に置く場合に使う）
+    pub fn edge_path_ranges(&self, u: usize, v: usize) -> PathRanges {
+        self.ranges(u, v, true)
+    }
+
+    fn ranges(&self, mut u: usize, mut v: usize, exclude_lca: bool) -> PathRanges {
+        let mut up = Vec::new();
+        let mut down = Vec::new();
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] > self.depth[self.head[v]] {
+                up.push((self.pos[self.head[u]], self.pos[u] + 1));
+                u = self.parent[self.head[u]];
+            } else {
+                down.push((self.pos[self.head[v]], self.pos[v] + 1));
+                v = self.parent[self.head[v]];
+            }
+        }
+        // 同じ heavy path 上の残り（浅い方が lca）
+        let skip = exclude_lca as usize;
+        if self.pos[u] >= self.pos[v] {
+            if self.pos[v] + skip <= self.pos[u] {
+                up.push((self.pos[v] + skip, self.pos[u] + 1));
+            }
+        } else {
+            down.push((self.pos[u] + skip, self.pos[v] + 1));
+        }
+        down.reverse();
+        (up, down)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::data_structures::Monoid;
+    use super::super::super::segment_tree::DynamicSegmentTree;
+    use super::super::Graph;
+    use super::*;
+    use super::super::super::test_util::{path_to_root, tree_path, XorShift};
+
+    // 列の連結（非可換）
+    struct Concat;
+
+    impl Monoid for Concat {
+        type S = Vec<usize>;
+        fn identity() -> Vec<usize> {
+            vec![]
+        }
+        fn op(a: &Vec<usize>, b: &Vec<usize>) -> Vec<usize> {
+            a.iter().chain(b).copied().collect()
+        }
+    }
+
+    #[test]
+    fn test_hld_path_fold_against_naive() {
+        let mut rng = XorShift::default();
+        for n in [1, 2, 5, 40] {
+            let mut parent = vec![usize::MAX; n];
+            let mut g = Graph::new_undirected(n);
+            for (i, p) in parent.iter_mut().enumerate().skip(1) {
+                // 長いパスができやすいように、直前の頂点を親に選びやすくする
+                *p = if rng.next(2) == 0 { i - 1 } else { rng.next(i) };
+                g.add_edge(*p, i, ());
+            }
+            let hld = Hld::new(&g, 0);
+
+            // 頂点の番号そのものを値とし、正順と逆順の 2 本のセグメント木に置く
+            let mut forward: DynamicSegmentTree<Concat> = DynamicSegmentTree::new(0, n as i64);
+            let mut backward: DynamicSegmentTree<Concat> = DynamicSegmentTree::new(0, n as i64);
+            for v in 0..n {
+                assert_eq!(hld.vertex_at(hld.pos(v)), v);
+                forward.set(hld.pos(v) as i64, vec![v]);
+                backward.set((n - 1 - hld.pos(v)) as i64, vec![v]);
+            }
+            let fold = |ranges: PathRanges| {
+                let mut result = vec![];
+                for (l, r) in ranges.0 {
+                    result = Concat::op(&result, &backward.prod((n - r) as i64, (n - l) as i64));
+                }
+                for (l, r) in ranges.1 {
+                    result = Concat::op(&result, &forward.prod(l as i64, r as i64));
+                }
+                result
+            };
+            for u in 0..n {
+                let pu = path_to_root(&parent, u);
+                // 部分木は連続区間
+                let (l, r) = hld.subtree_range(u);
+                let mut subtree: Vec<usize> = (l..r).map(|i| hld.vertex_at(i)).collect();
+                subtree.sort_unstable();
+                let expected: Vec<usize> = (0..n).filter(|&v| path_to_root(&parent, v).contains(&u)).collect();
+                assert_eq!(subtree, expected);
+
+                for v in 0..n {
+                    let pv = path_to_root(&parent, v);
+                    let w = *pu.iter().find(|x| pv.contains(x)).unwrap();
+                    let mut path = tree_path(&parent, u, v);
+                    assert_eq!(hld.lca(u, v), w);
+                    assert_eq!(hld.dist(u, v), path.len() - 1);
+                    assert_eq!(fold(hld.path_ranges(u, v)), path);
+                    path.retain(|&x| x != w);
+                    assert_eq!(fold(hld.edge_path_ranges(u, v)), path);
+                }
+            }
+        }
+    }
+}
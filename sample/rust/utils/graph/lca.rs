@@ -0,0 +1,253 @@
+use super::Adjacency;
+
+// 根付き木を BFS 順にたどり、(頂点の順序, 親, 深さ) を求める（再帰を使わない）
+pub(super) fn bfs_tree<G: Adjacency>(g: &G, root: usize) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+    let n = g.vertex_count();
+    let mut parent = vec![usize::MAX; n];
+    let mut depth = vec![0; n];
+    let mut order = Vec::with_capacity(n);
+    parent[root] = root;
+    order.push(root);
+    let mut head = 0;
+    while head < order.len() {
+        let u = order[head];
+        head += 1;
+        for e in g.neighbors(u) {
+            if parent[e.to] == usize::MAX {
+                parent[e.to] = u;
+                depth[e.to] = depth[u] + 1;
+                order.push(e.to);
+            }
+        }
+    }
+    assert_eq!(order.len(), n, "graph must be a connected tree");
+    (order, parent, depth)
+}
+
+// ダブリングによる最小共通祖先（構築 O(n log n)、クエリ O(log n)）
+pub struct Lca {
+    // up[k][v] は v の 2^k 個上の祖先（根より上は根）
+    up: Vec<Vec<usize>>,
+    depth: Vec<usize>,
+}
+
+impl Lca {
+    // 無向グラフとして与えられた木を root を根として構築する
+    pub fn new<G: Adjacency>(g: &G, root: usize) -> Self {
+        let (_, parent, depth) = bfs_tree(g, root);
+        let n = parent.len();
+        let log = (usize::BITS - n.leading_zeros()).max(1) as usize;
+        let mut up = vec![parent];
+        for k in 1..log {
+            let next = (0..n).map(|v| up[k - 1][up[k - 1][v]]).collect();
+            up.push(next);
+        }
+        Lca { up, depth }
+    }
+
+    // 根からの深さ
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    // 親（根なら None）
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        if self.depth[v] == 0 {
+            None
+        } else {
+            Some(self.up[0][v])
+        }
+    }
+
+    // v の k 個上の祖先（根より上なら None）
+    pub fn kth_ancestor(&self, mut v: usize, k: usize) -> Option<usize> {
+        if k > self.depth[v] {
+            return None;
+        }
+        for (i, up) in self.up.iter().enumerate() {
+            if k >> i & 1 == 1 {
+                v = up[v];
+            }
+        }
+        Some(v)
+    }
+
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        if self.depth[u] < self.depth[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+        u = self.kth_ancestor(u, self.depth[u] - self.depth[v]).unwrap();
+        if u == v {
+            return u;
+        }
+        for up in self.up.iter().rev() {
+            if up[u] != up[v] {
+                u = up[u];
+                v = up[v];
+            }
+        }
+        self.up[0][u]
+    }
+
+    // u と v の間の辺の本数
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    // u から v へのパス上で u から k 番目の頂点（k = 0 なら u、パスより長ければ None）
+    pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let w = self.lca(u, v);
+        let (du, dv) = (self.depth[u] - self.depth[w], self.depth[v] - self.depth[w]);
+        if k <= du {
+            self.kth_ancestor(u, k)
+        } else if k <= du + dv {
+            self.kth_ancestor(v, du + dv - k)
+        } else {
+            None
+        }
+    }
+}
+
+// オイラーツアーとスパーステーブルによる最小共通祖先（構築 O(n log n)、クエリ O(1)）
+pub struct EulerTourLca {
+    // first[v] はオイラーツアーで v が最初に現れる位置
+    first: Vec<usize>,
+    depth: Vec<usize>,
+    // table[k][i] はツアーの [i, i + 2^k) で最も浅い頂点
+    table: Vec<Vec<usize>>,
+}
+
+impl EulerTourLca {
+    // 無向グラフとして与えられた木を root を根として構築する
+    pub fn new<G: Adjacency>(g: &G, root: usize) -> Self {
+        let (_, parent, depth) = bfs_tree(g, root);
+        let n = parent.len();
+        let mut first = vec![0; n];
+        let mut tour = Vec::with_capacity(2 * n - 1);
+        // (頂点, 次に調べる辺の位置)
+        let mut stack = vec![(root, 0)];
+        tour.push(root);
+        while let Some(&(v, i)) = stack.last() {
+            let edges = g.neighbors(v);
+            if i < edges.len() {
+                stack.last_mut().unwrap().1 += 1;
+                let to = edges[i].to;
+                if to != root && parent[to] == v {
+                    first[to] = tour.len();
+                    tour.push(to);
+                    stack.push((to, 0));
+                }
+                continue;
+            }
+            stack.pop();
+            if let Some(&(p, _)) = stack.last() {
+                tour.push(p);
+            }
+        }
+
+        let mut table = vec![tour];
+        let mut k = 1;
+        while 1 << k <= table[0].len() {
+            let prev = &table[k - 1];
+            let next = (0..=table[0].len() - (1 << k))
+                .map(|i| {
+                    let (a, b) = (prev[i], prev[i + (1 << (k - 1))]);
+                    if depth[a] <= depth[b] {
+                        a
+                    } else {
+                        b
+                    }
+                })
+                .collect();
+            table.push(next);
+            k += 1;
+        }
+        EulerTourLca { first, depth, table }
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut l, mut r) = (self.first[u], self.first[v]);
+        if l > r {
+            std::mem::swap(&mut l, &mut r);
+        }
+        let k = (usize::BITS - 1 - (r - l + 1).leading_zeros()) as usize;
+        let (a, b) = (self.table[k][l], self.table[k][r + 1 - (1 << k)]);
+        if self.depth[a] <= self.depth[b] {
+            a
+        } else {
+            b
+        }
+    }
+
+    // u と v の間の辺の本数
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Graph;
+    use super::*;
+    use super::super::super::test_util::{path_to_root, tree_path, XorShift};
+
+    #[test]
+    fn test_lca_against_naive() {
+        let mut rng = XorShift::default();
+        for n in [1, 2, 3, 10, 60] {
+            // 頂点番号をシャッフルした、根が 0 とは限らない木
+            let mut label: Vec<usize> = (0..n).collect();
+            for i in (1..n).rev() {
+                label.swap(i, rng.next(i + 1));
+            }
+            let mut parent = vec![usize::MAX; n];
+            let mut g = Graph::new_undirected(n);
+            for i in 1..n {
+                let p = rng.next(i);
+                parent[label[i]] = label[p];
+                g.add_edge(label[i], label[p], ());
+            }
+            let root = label[0];
+            let lca = Lca::new(&g, root);
+            let euler = EulerTourLca::new(&g, root);
+            for u in 0..n {
+                let pu = path_to_root(&parent, u);
+                assert_eq!(lca.depth(u), pu.len() - 1);
+                assert_eq!(lca.parent(u), pu.get(1).copied());
+                for k in 0..n + 1 {
+                    assert_eq!(lca.kth_ancestor(u, k), pu.get(k).copied());
+                }
+                for v in 0..n {
+                    let pv = path_to_root(&parent, v);
+                    let w = *pu.iter().find(|x| pv.contains(x)).unwrap();
+                    assert_eq!(lca.lca(u, v), w);
+                    assert_eq!(euler.lca(u, v), w);
+                    let path = tree_path(&parent, u, v);
+                    assert_eq!(lca.dist(u, v), path.len() - 1);
+                    assert_eq!(euler.dist(u, v), path.len() - 1);
+                    for k in 0..path.len() + 1 {
+                        assert_eq!(lca.jump(u, v, k), path.get(k).copied());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lca_deep_path() {
+        // 再帰を使うとスタックが足りなくなる深さのパス
+        let n = 200_000;
+        let edges: Vec<(usize, usize, ())> = (1..n).map(|i| (i - 1, i, ())).collect();
+        let g = Graph::from_edges(n, &edges, false).to_csr();
+        let lca = Lca::new(&g, 0);
+        let euler = EulerTourLca::new(&g, 0);
+        assert_eq!(lca.lca(n - 1, n / 2), n / 2);
+        assert_eq!(euler.lca(n - 1, n / 2), n / 2);
+        assert_eq!(lca.jump(n / 2, n - 1, 3), Some(n / 2 + 3));
+        assert_eq!(euler.dist(0, n - 1), n - 1);
+    }
+}
@@ -0,0 +1,268 @@
+pub mod shortest_path;
+pub mod grid;
+pub mod scc;
+pub mod topological;
+pub mod lca;
+pub mod hld;
+pub mod rerooting;
+pub mod centroid;
+pub mod flow;
+
+use super::io::Scanner;
+use std::io::BufRead;
+use std::str::FromStr;
+
+// グラフの辺（隣接リストの要素）
+// 重みなしグラフでは W = ()、id は追加した順の辺番号（無向辺は両向きで同じ番号）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge<W> {
+    pub to: usize,
+    pub weight: W,
+    pub id: usize,
+}
+
+// 隣接リストを持つグラフの共通インターフェース
+// 探索や最短路などのアルゴリズムは Graph と Csr のどちらでも使えるようにこれを受け取る
+pub trait Adjacency {
+    type Weight;
+    // 頂点数
+    fn vertex_count(&self) -> usize;
+    // u から出る辺
+    fn neighbors(&self, u: usize) -> &[Edge<Self::Weight>];
+}
+
+// 隣接リストによるグラフ（辺の追加が可能）
+#[derive(Clone, Debug)]
+pub struct Graph<W> {
+    adj: Vec<Vec<Edge<W>>>,
+    // 追加した辺 (from, to, weight)
+    edges: Vec<(usize, usize, W)>,
+    directed: bool,
+}
+
+impl<W: Clone> Graph<W> {
+    // 頂点数 n の有向グラフ
+    pub fn new_directed(n: usize) -> Self {
+        Graph { adj: vec![Vec::new(); n], edges: Vec::new(), directed: true }
+    }
+
+    // 頂点数 n の無向グラフ
+    pub fn new_undirected(n: usize) -> Self {
+        Graph { adj: vec![Vec::new(); n], edges: Vec::new(), directed: false }
+    }
+
+    // 辺のリストからグラフを作成
+    pub fn from_edges(n: usize, edges: &[(usize, usize, W)], directed: bool) -> Self {
+        let mut g = if directed { Self::new_directed(n) } else { Self::new_undirected(n) };
+        for (u, v, w) in edges {
+            g.add_edge(*u, *v, w.clone());
+        }
+        g
+    }
+
+    // 辺 u -> v（無向グラフなら u - v）を追加して辺番号を返す
+    pub fn add_edge(&mut self, u: usize, v: usize, weight: W) -> usize {
+        let n = self.adj.len();
+        assert!(u < n && v < n, "edge ({}, {}) out of range for {} vertices", u, v, n);
+        let id = self.edges.len();
+        self.adj[u].push(Edge { to: v, weight: weight.clone(), id });
+        if !self.directed {
+            self.adj[v].push(Edge { to: u, weight: weight.clone(), id });
+        }
+        self.edges.push((u, v, weight));
+        id
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    // 辺の本数（無向辺は 1 本と数える）
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    // 追加した順の辺 (from, to, weight)
+    pub fn edges(&self) -> &[(usize, usize, W)] {
+        &self.edges
+    }
+
+    // 各頂点の出次数
+    pub fn out_degrees(&self) -> Vec<usize> {
+        self.adj.iter().map(|e| e.len()).collect()
+    }
+
+    // 辺の向きをすべて逆にしたグラフ（無向グラフはそのままのコピー）
+    pub fn reversed(&self) -> Self {
+        let mut g = Graph { adj: vec![Vec::new(); self.adj.len()], edges: Vec::new(), directed: self.directed };
+        for (u, v, w) in &self.edges {
+            g.add_edge(*v, *u, w.clone());
+        }
+        g
+    }
+
+    // CSR 形式に変換する
+    pub fn to_csr(&self) -> Csr<W> {
+        Csr::from_adjacency(self)
+    }
+}
+
+impl<W> Adjacency for Graph<W> {
+    type Weight = W;
+
+    fn vertex_count(&self) -> usize {
+        self.adj.len()
+    }
+
+    fn neighbors(&self, u: usize) -> &[Edge<W>] {
+        &self.adj[u]
+    }
+}
+
+// 1-indexed で "u v" が m 行並んだ重みなしの辺を読み取る
+pub fn read_unweighted<R: BufRead>(sc: &mut Scanner<R>, n: usize, m: usize, directed: bool) -> Graph<()> {
+    let mut g = if directed { Graph::new_directed(n) } else { Graph::new_undirected(n) };
+    for _ in 0..m {
+        let (u, v) = (sc.read_usize1(), sc.read_usize1());
+        g.add_edge(u, v, ());
+    }
+    g
+}
+
+// 1-indexed で "u v w" が m 行並んだ重み付きの辺を読み取る
+pub fn read_weighted<R: BufRead, W: Clone + FromStr>(sc: &mut Scanner<R>, n: usize, m: usize, directed: bool) -> Graph<W> {
+    let mut g = if directed { Graph::new_directed(n) } else { Graph::new_undirected(n) };
+    for _ in 0..m {
+        let (u, v) = (sc.read_usize1(), sc.read_usize1());
+        let w: W = sc.read();
+        g.add_edge(u, v, w);
+    }
+    g
+}
+
+// n 頂点の木（1-indexed の n - 1 本の辺）を読み取る
+pub fn read_tree<R: BufRead>(sc: &mut Scanner<R>, n: usize) -> Graph<()> {
+    read_unweighted(sc, n, n.saturating_sub(1), false)
+}
+
+// 2..=n 番目の頂点の親 p_2, ..., p_n（1-indexed）として与えられる根付き木を読み取る
+pub fn read_parents<R: BufRead>(sc: &mut Scanner<R>, n: usize) -> Graph<()> {
+    let mut g = Graph::new_undirected(n);
+    for v in 1..n {
+        let p = sc.read_usize1();
+        g.add_edge(p, v, ());
+    }
+    g
+}
+
+// CSR（Compressed Sparse Row）形式のグラフ
+// 辺を 1 本の配列に詰めて持つため、構築後は変更できないがキャッシュ効率が良い
+#[derive(Clone, Debug)]
+pub struct Csr<W> {
+    // 頂点 u の辺は edges[start[u]..start[u + 1]]
+    start: Vec<usize>,
+    edges: Vec<Edge<W>>,
+}
+
+impl<W: Clone> Csr<W> {
+    // 有向辺 (from, to, weight) のリストから作成（無向グラフは両向きの辺を渡す）
+    // 辺番号は渡した順
+    pub fn new(n: usize, edges: &[(usize, usize, W)]) -> Self {
+        let mut start = vec![0; n + 1];
+        for &(u, _, _) in edges {
+            start[u + 1] += 1;
+        }
+        for i in 0..n {
+            start[i + 1] += start[i];
+        }
+        let mut pos = start.clone();
+        let mut slots: Vec<Option<Edge<W>>> = vec![None; edges.len()];
+        for (id, (u, v, w)) in edges.iter().enumerate() {
+            slots[pos[*u]] = Some(Edge { to: *v, weight: w.clone(), id });
+            pos[*u] += 1;
+        }
+        Csr { start, edges: slots.into_iter().map(|e| e.unwrap()).collect() }
+    }
+
+    // 任意の隣接リストから作成（辺の並びと辺番号はそのまま）
+    pub fn from_adjacency<G: Adjacency<Weight = W>>(g: &G) -> Self {
+        let n = g.vertex_count();
+        let mut start = Vec::with_capacity(n + 1);
+        let mut edges = Vec::new();
+        start.push(0);
+        for u in 0..n {
+            edges.extend_from_slice(g.neighbors(u));
+            start.push(edges.len());
+        }
+        Csr { start, edges }
+    }
+}
+
+impl<W> Adjacency for Csr<W> {
+    type Weight = W;
+
+    fn vertex_count(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    fn neighbors(&self, u: usize) -> &[Edge<W>] {
+        &self.edges[self.start[u]..self.start[u + 1]]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets<G: Adjacency>(g: &G, u: usize) -> Vec<usize> {
+        g.neighbors(u).iter().map(|e| e.to).collect()
+    }
+
+    #[test]
+    fn test_read_graphs() {
+        let input = "4 3\n1 2\n2 3\n4 1\n";
+        let mut sc = Scanner::new(input.as_bytes());
+        let (n, m): (usize, usize) = (sc.read(), sc.read());
+        let g = read_unweighted(&mut sc, n, m, false);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(targets(&g, 0), vec![1, 3]);
+        assert_eq!(targets(&g, 1), vec![0, 2]);
+        assert_eq!(g.neighbors(3)[0], Edge { to: 0, weight: (), id: 2 });
+
+        // 辺が複数行にまたがっていても読める
+        let input = "3 2\n1 2 5 3\n1 -7\n";
+        let mut sc = Scanner::new(input.as_bytes());
+        let (n, m): (usize, usize) = (sc.read(), sc.read());
+        let g: Graph<i64> = read_weighted(&mut sc, n, m, true);
+        assert!(g.is_directed());
+        assert_eq!(g.edges(), &[(0, 1, 5), (2, 0, -7)]);
+        assert!(g.neighbors(1).is_empty());
+        assert_eq!(targets(&g.reversed(), 0), vec![2]);
+
+        let mut sc = Scanner::new("1 1 2\n".as_bytes());
+        let g = read_parents(&mut sc, 4);
+        assert_eq!(targets(&g, 0), vec![1, 2]);
+        assert_eq!(targets(&g, 1), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_csr_matches_graph() {
+        let edges = [(0, 1, 3), (2, 0, 1), (0, 2, 4), (1, 1, 2), (3, 0, 9)];
+        for directed in [true, false] {
+            let g = Graph::from_edges(4, &edges, directed);
+            let csr = g.to_csr();
+            assert_eq!(csr.vertex_count(), 4);
+            for u in 0..4 {
+                assert_eq!(csr.neighbors(u), g.neighbors(u));
+            }
+        }
+        let csr = Csr::new(4, &edges);
+        assert_eq!(targets(&csr, 0), vec![1, 2]);
+        assert_eq!(csr.neighbors(3), &[Edge { to: 0, weight: 9, id: 4 }]);
+        assert_eq!(degrees(&csr), vec![2, 1, 1, 1]);
+    }
+
+    fn degrees<G: Adjacency>(g: &G) -> Vec<usize> {
+        (0..g.vertex_count()).map(|u| g.neighbors(u).len()).collect()
+    }
+}
@@ -0,0 +1,213 @@
+use super::lca::bfs_tree;
+use super::Adjacency;
+
+// 全方位木 DP（すべての頂点を根としたときの木 DP の値を O(n) でまとめて求める）
+// 頂点 v を根とする部分木の値は add_root(merge(lift(子の部分木の値, 辺の重み), ...), v) で計算する
+// W は辺の重みの型（重みなしグラフなら ()）
+pub trait Rerooting<W> {
+    type Value: Clone;
+
+    // merge の単位元（子のない頂点では、これに add_root を適用する）
+    fn identity(&self) -> Self::Value;
+    // 子からの値をまとめる（結合的かつ可換であること）
+    fn merge(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+    // 子の部分木の値を、親との辺を通して親の側に持ち上げる
+    fn lift(&self, value: &Self::Value, weight: &W) -> Self::Value;
+    // 子からの値をまとめたものに頂点 v を加えて、v を根とする部分木の値にする
+    fn add_root(&self, merged: &Self::Value, v: usize) -> Self::Value;
+
+    // 無向グラフとして与えられた木で、各頂点を根としたときの値（再帰を使わない）
+    fn solve<G: Adjacency<Weight = W>>(&self, g: &G) -> Vec<Self::Value>
+    where
+        Self: Sized,
+    {
+        let n = g.vertex_count();
+        if n == 0 {
+            return vec![];
+        }
+        let root = 0;
+        let (order, parent, _) = bfs_tree(g, root);
+        let is_parent_edge = |v: usize, to: usize| v != root && to == parent[v];
+
+        // 頂点 0 を根としたときの部分木の値
+        let mut down = vec![self.identity(); n];
+        for &v in order.iter().rev() {
+            let mut acc = self.identity();
+            for e in g.neighbors(v) {
+                if !is_parent_edge(v, e.to) {
+                    acc = self.merge(&acc, &self.lift(&down[e.to], &e.weight));
+                }
+            }
+            down[v] = self.add_root(&acc, v);
+        }
+
+        // 親の側の部分木の値を持ち上げたもの（根では使わない）
+        let mut up = vec![self.identity(); n];
+        let mut answer = vec![self.identity(); n];
+        for &v in &order {
+            let edges = g.neighbors(v);
+            let lifted: Vec<Self::Value> = edges
+                .iter()
+                .map(|e| if is_parent_edge(v, e.to) { up[v].clone() } else { self.lift(&down[e.to], &e.weight) })
+                .collect();
+            let mut suffix = vec![self.identity(); edges.len() + 1];
+            for i in (0..edges.len()).rev() {
+                suffix[i] = self.merge(&lifted[i], &suffix[i + 1]);
+            }
+            answer[v] = self.add_root(&suffix[0], v);
+            // 子 c から見た v の側の部分木は、c 以外からの値をまとめたもの
+            let mut prefix = self.identity();
+            for (i, e) in edges.iter().enumerate() {
+                if !is_parent_edge(v, e.to) {
+                    let rest = self.merge(&prefix, &suffix[i + 1]);
+                    up[e.to] = self.lift(&self.add_root(&rest, v), &e.weight);
+                }
+                prefix = self.merge(&prefix, &lifted[i]);
+            }
+        }
+        answer
+    }
+}
+
+// 各頂点から最も遠い頂点までの距離（木の離心数、辺の重みは非負）
+// 重みなしグラフ（W = ()）には FarthestDistanceUnweighted を使う
+pub struct FarthestDistance;
+
+impl<W: Copy + Into<i64>> Rerooting<W> for FarthestDistance {
+    type Value = i64;
+
+    fn identity(&self) -> i64 {
+        0
+    }
+
+    fn merge(&self, a: &i64, b: &i64) -> i64 {
+        *a.max(b)
+    }
+
+    fn lift(&self, value: &i64, weight: &W) -> i64 {
+        value + (*weight).into()
+    }
+
+    fn add_root(&self, merged: &i64, _v: usize) -> i64 {
+        *merged
+    }
+}
+
+// 重みなしグラフ（read_tree などで読んだ Graph<()>）の離心数（辺の本数で数える）
+pub struct FarthestDistanceUnweighted;
+
+impl Rerooting<()> for FarthestDistanceUnweighted {
+    type Value = i64;
+
+    fn identity(&self) -> i64 {
+        0
+    }
+
+    fn merge(&self, a: &i64, b: &i64) -> i64 {
+        *a.max(b)
+    }
+
+    fn lift(&self, value: &i64, _weight: &()) -> i64 {
+        value + 1
+    }
+
+    fn add_root(&self, merged: &i64, _v: usize) -> i64 {
+        *merged
+    }
+}
+
+// 各頂点を根としたときの、全頂点の部分木のサイズの和（= 根からの距離の和 + n）
+// 値は (部分木のサイズ, 部分木内の部分木のサイズの和) で、答えは 2 つ目の要素
+pub struct SubtreeSizeSum;
+
+impl<W> Rerooting<W> for SubtreeSizeSum {
+    type Value = (u64, u64);
+
+    fn identity(&self) -> (u64, u64) {
+        (0, 0)
+    }
+
+    fn merge(&self, a: &(u64, u64), b: &(u64, u64)) -> (u64, u64) {
+        (a.0 + b.0, a.1 + b.1)
+    }
+
+    fn lift(&self, value: &(u64, u64), _weight: &W) -> (u64, u64) {
+        *value
+    }
+
+    fn add_root(&self, merged: &(u64, u64), _v: usize) -> (u64, u64) {
+        (merged.0 + 1, merged.1 + merged.0 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Graph;
+    use super::*;
+    use super::super::super::test_util::{tree_distances, XorShift};
+
+    // 頂点にも値がある木で、根から始まるパスの重みの最大値（add_root が頂点に依存する例）
+    struct WeightedVertices(Vec<i64>);
+
+    impl Rerooting<i64> for WeightedVertices {
+        type Value = i64;
+        fn identity(&self) -> i64 {
+            0
+        }
+        fn merge(&self, a: &i64, b: &i64) -> i64 {
+            *a.max(b)
+        }
+        fn lift(&self, value: &i64, weight: &i64) -> i64 {
+            value + weight
+        }
+        fn add_root(&self, merged: &i64, v: usize) -> i64 {
+            merged + self.0[v]
+        }
+    }
+
+    #[test]
+    fn test_rerooting_against_brute_force() {
+        let mut rng = XorShift::default();
+        for n in [1, 2, 3, 8, 50] {
+            let mut g = Graph::new_undirected(n);
+            for i in 1..n {
+                g.add_edge(rng.next(i), i, rng.next(10) as i64);
+            }
+            let vertex_weight: Vec<i64> = (0..n).map(|_| rng.next(5) as i64).collect();
+            let farthest = FarthestDistance.solve(&g);
+            let size_sum = SubtreeSizeSum.solve(&g);
+            let unweighted = Graph::from_edges(n, &g.edges().iter().map(|&(u, v, _)| (u, v, ())).collect::<Vec<_>>(), false);
+            let hops = FarthestDistanceUnweighted.solve(&unweighted);
+            let weighted = WeightedVertices(vertex_weight.clone()).solve(&g);
+            for r in 0..n {
+                let dist: Vec<(i64, u64)> = tree_distances(&g, r).into_iter().map(Option::unwrap).collect();
+                assert_eq!(farthest[r], dist.iter().map(|d| d.0).max().unwrap());
+                assert_eq!(hops[r], dist.iter().map(|d| d.1 as i64).max().unwrap());
+                assert_eq!(size_sum[r], (n as u64, dist.iter().map(|d| d.1 + 1).sum::<u64>()));
+                // r から v へのパス上の頂点の値の和 + 辺の重みの和の最大値
+                let expected = (0..n)
+                    .map(|v| {
+                        let dv = tree_distances(&g, v);
+                        dist[v].0 + (0..n).filter(|&x| dist[x].1 + dv[x].unwrap().1 == dist[v].1).map(|x| vertex_weight[x]).sum::<i64>()
+                    })
+                    .max()
+                    .unwrap();
+                assert_eq!(weighted[r], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rerooting_unweighted_deep_path() {
+        let n = 200_000;
+        let edges: Vec<(usize, usize, ())> = (1..n).map(|i| (i - 1, i, ())).collect();
+        let g = Graph::from_edges(n, &edges, false);
+        let size_sum = SubtreeSizeSum.solve(&g);
+        let farthest = FarthestDistanceUnweighted.solve(&g);
+        for v in [0, 1, n / 2, n - 1] {
+            assert_eq!(farthest[v], v.max(n - 1 - v) as i64);
+            let (l, r) = (v as u64, (n - 1 - v) as u64);
+            assert_eq!(size_sum[v].1, l * (l + 1) / 2 + r * (r + 1) / 2 + n as u64);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use super::{Adjacency, Graph};
+
+// 強連結成分分解の結果
+// 成分はトポロジカル順に番号付けされ、辺 u -> v があれば comp[u] <= comp[v]
+#[derive(Clone, Debug)]
+pub struct Scc {
+    // 各頂点の成分番号
+    pub comp: Vec<usize>,
+    // 各成分に属する頂点
+    pub components: Vec<Vec<usize>>,
+}
+
+// Tarjan 法による強連結成分分解（O(n + m)、再帰を使わない）
+pub fn scc<G: Adjacency>(g: &G) -> Scc {
+    const UNVISITED: usize = usize::MAX;
+    let n = g.vertex_count();
+    let mut order = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    // 見つかった順（トポロジカル順の逆）の成分番号
+    let mut comp = vec![0; n];
+    let mut count = 0;
+    let mut time = 0;
+    // (頂点, 次に調べる辺の位置)
+    let mut call_stack: Vec<(usize, usize)> = Vec::new();
+
+    for root in 0..n {
+        if order[root] != UNVISITED {
+            continue;
+        }
+        call_stack.push((root, 0));
+        while let Some(&(v, i)) = call_stack.last() {
+            if i == 0 {
+                order[v] = time;
+                low[v] = time;
+                time += 1;
+                stack.push(v);
+                on_stack[v] = true;
+            }
+            let edges = g.neighbors(v);
+            if i < edges.len() {
+                let to = edges[i].to;
+                call_stack.last_mut().unwrap().1 += 1;
+                if order[to] == UNVISITED {
+                    call_stack.push((to, 0));
+                } else if on_stack[to] {
+                    low[v] = low[v].min(order[to]);
+                }
+                continue;
+            }
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == order[v] {
+                loop {
+                    let x = stack.pop().unwrap();
+                    on_stack[x] = false;
+                    comp[x] = count;
+                    if x == v {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+        }
+    }
+
+    // 番号を反転してトポロジカル順にする
+    let mut components = vec![Vec::new(); count];
+    for v in 0..n {
+        comp[v] = count - 1 - comp[v];
+        components[comp[v]].push(v);
+    }
+    Scc { comp, components }
+}
+
+impl Scc {
+    // 成分の個数
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    // 成分を 1 頂点に縮約した DAG（多重辺と自己ループは除く）
+    // 頂点番号は成分番号なので、0, 1, ... の順がそのままトポロジカル順
+    pub fn condensation<G: Adjacency>(&self, g: &G) -> Graph<()> {
+        let mut edges = Vec::new();
+        for u in 0..g.vertex_count() {
+            for e in g.neighbors(u) {
+                let (a, b) = (self.comp[u], self.comp[e.to]);
+                if a != b {
+                    edges.push((a, b));
+                }
+            }
+        }
+        edges.sort_unstable();
+        edges.dedup();
+        let mut dag = Graph::new_directed(self.len());
+        for (a, b) in edges {
+            dag.add_edge(a, b, ());
+        }
+        dag
+    }
+}
+
+// 2-SAT（n 個の変数について「x_i = f または x_j = g」の形の節をすべて満たす割り当てを求める）
+pub struct TwoSat {
+    n: usize,
+    // 頂点 2i + 1 が「x_i = true」、2i が「x_i = false」
+    graph: Graph<()>,
+}
+
+impl TwoSat {
+    pub fn new(n: usize) -> Self {
+        TwoSat { n, graph: Graph::new_directed(2 * n) }
+    }
+
+    fn literal(i: usize, f: bool) -> usize {
+        2 * i + f as usize
+    }
+
+    // 節「x_i = f または x_j = g」を追加する
+    pub fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        assert!(i < self.n && j < self.n);
+        self.graph.add_edge(Self::literal(i, !f), Self::literal(j, g), ());
+        self.graph.add_edge(Self::literal(j, !g), Self::literal(i, f), ());
+    }
+
+    // 満たす割り当てがあれば返す（O(n + 節の個数)）
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let scc = scc(&self.graph);
+        (0..self.n)
+            .map(|i| {
+                let (t, f) = (scc.comp[Self::literal(i, true)], scc.comp[Self::literal(i, false)]);
+                // トポロジカル順で後ろにあるリテラルを真にする
+                if t == f {
+                    None
+                } else {
+                    Some(t > f)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::super::test_util::XorShift;
+
+    #[test]
+    fn test_scc_against_reachability() {
+        let mut rng = XorShift::default();
+        for _ in 0..100 {
+            let n = 1 + rng.next(15);
+            let m = rng.next(3 * n);
+            let mut g = Graph::new_directed(n);
+            let mut reach = vec![vec![false; n]; n];
+            for (v, row) in reach.iter_mut().enumerate() {
+                row[v] = true;
+            }
+            for _ in 0..m {
+                let (u, v) = (rng.next(n), rng.next(n));
+                g.add_edge(u, v, ());
+                reach[u][v] = true;
+            }
+            for k in 0..n {
+                for i in 0..n {
+                    for j in 0..n {
+                        reach[i][j] |= reach[i][k] && reach[k][j];
+                    }
+                }
+            }
+
+            let result = scc(&g);
+            for (u, row) in reach.iter().enumerate() {
+                for (v, &uv) in row.iter().enumerate() {
+                    assert_eq!(result.comp[u] == result.comp[v], uv && reach[v][u]);
+                }
+                assert!(result.components[result.comp[u]].contains(&u));
+            }
+            let dag = result.condensation(&g);
+            for (a, b, _) in dag.edges() {
+                assert!(a < b);
+            }
+            for u in 0..n {
+                for e in g.neighbors(u) {
+                    let (a, b) = (result.comp[u], result.comp[e.to]);
+                    assert!(a <= b);
+                    assert_eq!(a < b, dag.neighbors(a).iter().filter(|x| x.to == b).count() == 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_two_sat_against_brute_force() {
+        let mut rng = XorShift::new(2463534242);
+        for _ in 0..200 {
+            let n = 1 + rng.next(6);
+            let clauses: Vec<(usize, bool, usize, bool)> =
+                (0..rng.next(12)).map(|_| (rng.next(n), rng.next(2) == 1, rng.next(n), rng.next(2) == 1)).collect();
+            let mut ts = TwoSat::new(n);
+            for &(i, f, j, g) in &clauses {
+                ts.add_clause(i, f, j, g);
+            }
+            let satisfies = |x: &[bool]| clauses.iter().all(|&(i, f, j, g)| x[i] == f || x[j] == g);
+            let brute = (0..1usize << n).any(|mask| satisfies(&(0..n).map(|i| mask >> i & 1 == 1).collect::<Vec<_>>()));
+            match ts.solve() {
+                Some(x) => assert!(satisfies(&x)),
+                None => assert!(!brute),
+            }
+            assert_eq!(ts.solve().is_some(), brute);
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use super::Adjacency;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+// 単一始点最短路の結果
+// dist[v] は始点からの距離（到達不能なら None）、prev[v] は最短路木での v の親
+#[derive(Clone, Debug)]
+pub struct ShortestPath {
+    pub dist: Vec<Option<i64>>,
+    pub prev: Vec<Option<usize>>,
+}
+
+impl ShortestPath {
+    fn new(n: usize, s: usize) -> Self {
+        let mut result = ShortestPath { dist: vec![None; n], prev: vec![None; n] };
+        result.dist[s] = Some(0);
+        result
+    }
+
+    // 始点から t への最短路の頂点列（到達不能なら None）
+    pub fn path(&self, t: usize) -> Option<Vec<usize>> {
+        self.dist[t]?;
+        Some(restore_path(&self.prev, t))
+    }
+}
+
+// 親の配列をたどって根から t までの頂点列を復元する
+pub fn restore_path(prev: &[Option<usize>], t: usize) -> Vec<usize> {
+    let mut path = vec![t];
+    let mut v = t;
+    while let Some(p) = prev[v] {
+        path.push(p);
+        v = p;
+        assert!(path.len() <= prev.len(), "prev contains a cycle");
+    }
+    path.reverse();
+    path
+}
+
+// 重みを無視した BFS（辺の本数が距離、O(n + m)）
+pub fn bfs<G: Adjacency>(g: &G, s: usize) -> ShortestPath {
+    let mut result = ShortestPath::new(g.vertex_count(), s);
+    let mut queue = VecDeque::from(vec![s]);
+    while let Some(u) = queue.pop_front() {
+        let d = result.dist[u].unwrap();
+        for e in g.neighbors(u) {
+            if result.dist[e.to].is_none() {
+                result.dist[e.to] = Some(d + 1);
+                result.prev[e.to] = Some(u);
+                queue.push_back(e.to);
+            }
+        }
+    }
+    result
+}
+
+// 辺の重みが 0 か 1 のグラフでの 0-1 BFS（O(n + m)）
+pub fn bfs_01<G>(g: &G, s: usize) -> ShortestPath
+where
+    G: Adjacency,
+    G::Weight: Copy + Into<i64>,
+{
+    let mut result = ShortestPath::new(g.vertex_count(), s);
+    let mut deque = VecDeque::from(vec![(0, s)]);
+    while let Some((d, u)) = deque.pop_front() {
+        if result.dist[u] != Some(d) {
+            continue;
+        }
+        for e in g.neighbors(u) {
+            let w: i64 = e.weight.into();
+            assert!(w == 0 || w == 1, "0-1 BFS requires weights 0 or 1");
+            let nd = d + w;
+            if result.dist[e.to].is_none_or(|old| nd < old) {
+                result.dist[e.to] = Some(nd);
+                result.prev[e.to] = Some(u);
+                if w == 0 {
+                    deque.push_front((nd, e.to));
+                } else {
+                    deque.push_back((nd, e.to));
+                }
+            }
+        }
+    }
+    result
+}
+
+// 非負の重みのグラフでの Dijkstra 法（O((n + m) log n)）
+pub fn dijkstra<G>(g: &G, s: usize) -> ShortestPath
+where
+    G: Adjacency,
+    G::Weight: Copy + Into<i64>,
+{
+    let mut result = ShortestPath::new(g.vertex_count(), s);
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((0i64, s)));
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if result.dist[u] != Some(d) {
+            continue;
+        }
+        for e in g.neighbors(u) {
+            let w: i64 = e.weight.into();
+            assert!(w >= 0, "Dijkstra requires non-negative weights");
+            let nd = d.saturating_add(w);
+            if result.dist[e.to].is_none_or(|old| nd < old) {
+                result.dist[e.to] = Some(nd);
+                result.prev[e.to] = Some(u);
+                heap.push(Reverse((nd, e.to)));
+            }
+        }
+    }
+    result
+}
+
+// 負の重みを含むグラフでの Bellman-Ford 法（O(nm)）
+// 返り値の 2 つ目は始点から到達可能な負閉路の影響を受ける（距離が -∞ になる）頂点
+// そのような頂点の dist と prev は None にする
+pub fn bellman_ford<G>(g: &G, s: usize) -> (ShortestPath, Vec<bool>)
+where
+    G: Adjacency,
+    G::Weight: Copy + Into<i64>,
+{
+    let n = g.vertex_count();
+    let mut result = ShortestPath::new(n, s);
+    // 1 辺でも緩和できる間は繰り返す（負閉路がなければ n - 1 回で収束）
+    for _ in 1..n {
+        let mut updated = false;
+        for u in 0..n {
+            let Some(d) = result.dist[u] else { continue };
+            for e in g.neighbors(u) {
+                let nd = d.saturating_add(e.weight.into());
+                if result.dist[e.to].is_none_or(|old| nd < old) {
+                    result.dist[e.to] = Some(nd);
+                    result.prev[e.to] = Some(u);
+                    updated = true;
+                }
+            }
+        }
+        if !updated {
+            break;
+        }
+    }
+
+    // まだ緩和できる辺の先と、そこから到達できる頂点が負閉路の影響を受ける
+    let mut negative = vec![false; n];
+    let mut queue = VecDeque::new();
+    for u in 0..n {
+        let Some(d) = result.dist[u] else { continue };
+        for e in g.neighbors(u) {
+            let nd = d.saturating_add(e.weight.into());
+            if nd < result.dist[e.to].unwrap() && !negative[e.to] {
+                negative[e.to] = true;
+                queue.push_back(e.to);
+            }
+        }
+    }
+    while let Some(u) = queue.pop_front() {
+        for e in g.neighbors(u) {
+            if !negative[e.to] {
+                negative[e.to] = true;
+                queue.push_back(e.to);
+            }
+        }
+    }
+    for (v, &neg) in negative.iter().enumerate() {
+        if neg {
+            result.dist[v] = None;
+            result.prev[v] = None;
+        }
+    }
+    (result, negative)
+}
+
+// 全点対間最短路（Floyd-Warshall 法）の結果
+pub struct AllPairsShortestPath {
+    dist: Vec<Vec<Option<i64>>>,
+    // next[u][v] は u から v への最短路で u の次の頂点
+    next: Vec<Vec<Option<usize>>>,
+}
+
+// Floyd-Warshall 法（O(n^3)、負の重みも可）
+pub fn floyd_warshall<G>(g: &G) -> AllPairsShortestPath
+where
+    G: Adjacency,
+    G::Weight: Copy + Into<i64>,
+{
+    let n = g.vertex_count();
+    let mut dist = vec![vec![None; n]; n];
+    let mut next = vec![vec![None; n]; n];
+    for u in 0..n {
+        dist[u][u] = Some(0);
+        next[u][u] = Some(u);
+    }
+    for u in 0..n {
+        for e in g.neighbors(u) {
+            let w: i64 = e.weight.into();
+            if dist[u][e.to].is_none_or(|old| w < old) {
+                dist[u][e.to] = Some(w);
+                next[u][e.to] = Some(e.to);
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            let Some(dik) = dist[i][k] else { continue };
+            for j in 0..n {
+                let Some(dkj) = dist[k][j] else { continue };
+                let nd = dik.saturating_add(dkj);
+                if dist[i][j].is_none_or(|old| nd < old) {
+                    dist[i][j] = Some(nd);
+                    next[i][j] = next[i][k];
+                }
+            }
+        }
+    }
+    AllPairsShortestPath { dist, next }
+}
+
+impl AllPairsShortestPath {
+    // 負閉路が存在するか
+    pub fn has_negative_cycle(&self) -> bool {
+        (0..self.dist.len()).any(|v| self.dist[v][v].is_some_and(|d| d < 0))
+    }
+
+    // u から v への経路が負閉路を通っていくらでも短くできるか（O(n)）
+    pub fn is_unbounded(&self, u: usize, v: usize) -> bool {
+        (0..self.dist.len()).any(|k| self.dist[k][k].is_some_and(|d| d < 0) && self.dist[u][k].is_some() && self.dist[k][v].is_some())
+    }
+
+    // u から v への最短距離（到達不能なら None、負閉路の影響を受ける場合は意味を持たない）
+    pub fn dist(&self, u: usize, v: usize) -> Option<i64> {
+        self.dist[u][v]
+    }
+
+    // u から v への最短路の頂点列（到達不能か、負閉路の影響を受ける場合は None）
+    pub fn path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+        if self.dist[u][v].is_none() || self.is_unbounded(u, v) {
+            return None;
+        }
+        let mut path = vec![u];
+        let mut x = u;
+        while x != v {
+            x = self.next[x][v].unwrap();
+            path.push(x);
+        }
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Graph;
+    use super::*;
+    use super::super::super::test_util::XorShift;
+
+    fn random_graph(rng: &mut XorShift, n: usize, m: usize, lo: i64, hi: i64) -> Graph<i64> {
+        let mut g = Graph::new_directed(n);
+        for _ in 0..m {
+            let (u, v) = (rng.next(n), rng.next(n));
+            g.add_edge(u, v, lo + rng.next((hi - lo + 1) as usize) as i64);
+        }
+        g
+    }
+
+    // 経路の長さ（連続する頂点間の最小の重みの辺を使う）
+    fn path_length(g: &Graph<i64>, path: &[usize]) -> i64 {
+        path.windows(2).map(|w| g.neighbors(w[0]).iter().filter(|e| e.to == w[1]).map(|e| e.weight).min().unwrap()).sum()
+    }
+
+    #[test]
+    fn test_single_source_against_floyd_warshall() {
+        let mut rng = XorShift::default();
+        for _ in 0..50 {
+            let n = 1 + rng.next(12);
+            let m = rng.next(40);
+            let g = random_graph(&mut rng, n, m, 0, 20);
+            let all = floyd_warshall(&g);
+            assert!(!all.has_negative_cycle());
+            let s = rng.next(n);
+            let sp = dijkstra(&g, s);
+            let (bf, negative) = bellman_ford(&g, s);
+            assert!(negative.iter().all(|&x| !x));
+            for v in 0..n {
+                assert_eq!(sp.dist[v], all.dist(s, v));
+                assert_eq!(bf.dist[v], all.dist(s, v));
+                if let Some(d) = sp.dist[v] {
+                    for path in [sp.path(v).unwrap(), bf.path(v).unwrap(), all.path(s, v).unwrap()] {
+                        assert_eq!((path[0], *path.last().unwrap()), (s, v));
+                        assert_eq!(path_length(&g, &path), d);
+                    }
+                } else {
+                    assert_eq!(sp.path(v), None);
+                }
+            }
+
+            // 重み 0/1 と重みなし
+            let g01 = Graph::from_edges(n, &g.edges().iter().map(|&(u, v, w)| (u, v, w % 2)).collect::<Vec<_>>(), true);
+            let unit = Graph::from_edges(n, &g.edges().iter().map(|&(u, v, _)| (u, v, 1i64)).collect::<Vec<_>>(), true);
+            assert_eq!(bfs_01(&g01, s).dist, dijkstra(&g01, s).dist);
+            assert_eq!(bfs(&g, s).dist, dijkstra(&unit, s).dist);
+        }
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        // 0 -> 1 -> 2 -> 1 が負閉路、3 はその先、4 は到達不能、5 は負閉路と無関係
+        let edges = [(0, 1, 1), (1, 2, -3), (2, 1, 1), (2, 3, 5), (4, 1, 0), (0, 5, 7), (3, 3, 0)];
+        let g = Graph::from_edges(6, &edges, true);
+        let (sp, negative) = bellman_ford(&g, 0);
+        assert_eq!(negative, vec![false, true, true, true, false, false]);
+        assert_eq!(sp.dist, vec![Some(0), None, None, None, None, Some(7)]);
+        assert_eq!(sp.path(5), Some(vec![0, 5]));
+
+        let all = floyd_warshall(&g);
+        assert!(all.has_negative_cycle());
+        assert!(all.is_unbounded(0, 3) && all.is_unbounded(4, 2));
+        assert!(!all.is_unbounded(0, 5) && !all.is_unbounded(3, 3));
+        assert_eq!(all.path(0, 3), None);
+        assert_eq!(all.path(0, 5), Some(vec![0, 5]));
+
+        // 負の辺はあるが負閉路はない
+        let g = Graph::from_edges(3, &[(0, 1, 4), (0, 2, 1), (2, 1, -2)], true);
+        let (sp, negative) = bellman_ford(&g, 0);
+        assert!(negative.iter().all(|&x| !x));
+        assert_eq!(sp.dist, vec![Some(0), Some(-1), Some(1)]);
+        assert_eq!(sp.path(1), Some(vec![0, 2, 1]));
+    }
+}
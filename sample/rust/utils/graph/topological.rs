@@ -0,0 +1,222 @@
+use super::Adjacency;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+// 有向閉路（cycle[0] -> cycle[1] -> ... -> cycle[k - 1] -> cycle[0] の順に辺がある）
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle(pub Vec<usize>);
+
+fn in_degrees<G: Adjacency>(g: &G) -> Vec<usize> {
+    let mut indeg = vec![0; g.vertex_count()];
+    for u in 0..g.vertex_count() {
+        for e in g.neighbors(u) {
+            indeg[e.to] += 1;
+        }
+    }
+    indeg
+}
+
+// Kahn 法が途中で止まったときに、取り出されなかった頂点から閉路を 1 つ見つける
+// 残った頂点はどれも残った頂点からの入辺を持つので、入辺を逆にたどれば必ず閉路に入る
+fn find_cycle<G: Adjacency>(g: &G, removed: &[bool]) -> Cycle {
+    let n = g.vertex_count();
+    let mut pred = vec![usize::MAX; n];
+    for u in (0..n).filter(|&u| !removed[u]) {
+        for e in g.neighbors(u) {
+            if !removed[e.to] {
+                pred[e.to] = u;
+            }
+        }
+    }
+    let mut seen = vec![false; n];
+    let mut v = (0..n).find(|&u| !removed[u]).unwrap();
+    while !seen[v] {
+        seen[v] = true;
+        v = pred[v];
+    }
+    let mut cycle = vec![v];
+    let mut u = pred[v];
+    while u != v {
+        cycle.push(u);
+        u = pred[u];
+    }
+    cycle.reverse();
+    Cycle(cycle)
+}
+
+// Kahn 法によるトポロジカルソート（O(n + m)）
+// 閉路があれば、その閉路を 1 つ返す
+pub fn topological_sort<G: Adjacency>(g: &G) -> Result<Vec<usize>, Cycle> {
+    let n = g.vertex_count();
+    let mut indeg = in_degrees(g);
+    let mut queue: VecDeque<usize> = (0..n).filter(|&v| indeg[v] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(u) = queue.pop_front() {
+        order.push(u);
+        for e in g.neighbors(u) {
+            indeg[e.to] -= 1;
+            if indeg[e.to] == 0 {
+                queue.push_back(e.to);
+            }
+        }
+    }
+    finish(g, order)
+}
+
+// 辞書順最小のトポロジカルソート（O((n + m) log n)）
+pub fn topological_sort_min<G: Adjacency>(g: &G) -> Result<Vec<usize>, Cycle> {
+    let n = g.vertex_count();
+    let mut indeg = in_degrees(g);
+    let mut heap: BinaryHeap<Reverse<usize>> = (0..n).filter(|&v| indeg[v] == 0).map(Reverse).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse(u)) = heap.pop() {
+        order.push(u);
+        for e in g.neighbors(u) {
+            indeg[e.to] -= 1;
+            if indeg[e.to] == 0 {
+                heap.push(Reverse(e.to));
+            }
+        }
+    }
+    finish(g, order)
+}
+
+fn finish<G: Adjacency>(g: &G, order: Vec<usize>) -> Result<Vec<usize>, Cycle> {
+    if order.len() == g.vertex_count() {
+        return Ok(order);
+    }
+    let mut removed = vec![false; g.vertex_count()];
+    for &v in &order {
+        removed[v] = true;
+    }
+    Err(find_cycle(g, &removed))
+}
+
+// DAG 上の最長路（各頂点で終わるパスの長さの最大値、辺の長さは weight で与える）
+// 重みなしグラフなら weight に |_| 1 を渡すと辺の本数になる
+pub fn longest_path<G: Adjacency>(g: &G, weight: impl Fn(&G::Weight) -> i64) -> Result<Vec<i64>, Cycle> {
+    let order = topological_sort(g)?;
+    let mut dp = vec![0; g.vertex_count()];
+    for u in order {
+        for e in g.neighbors(u) {
+            dp[e.to] = dp[e.to].max(dp[u] + weight(&e.weight));
+        }
+    }
+    Ok(dp)
+}
+
+// DAG 上で s から各頂点へのパスの個数を modulo で割った余り
+// 多重辺は別のパスとして数える（s から到達できない閉路があっても Err になる）
+pub fn count_paths<G: Adjacency>(g: &G, s: usize, modulo: u64) -> Result<Vec<u64>, Cycle> {
+    let order = topological_sort(g)?;
+    let mut dp = vec![0; g.vertex_count()];
+    dp[s] = 1 % modulo;
+    for u in order {
+        if dp[u] == 0 {
+            continue;
+        }
+        for e in g.neighbors(u) {
+            dp[e.to] = (dp[e.to] + dp[u]) % modulo;
+        }
+    }
+    Ok(dp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Graph;
+    use super::*;
+    use super::super::super::test_util::XorShift;
+
+    fn is_topological(g: &Graph<i64>, order: &[usize]) -> bool {
+        let mut pos = vec![usize::MAX; g.vertex_count()];
+        for (i, &v) in order.iter().enumerate() {
+            pos[v] = i;
+        }
+        pos.iter().all(|&p| p != usize::MAX) && g.edges().iter().all(|&(u, v, _)| pos[u] < pos[v])
+    }
+
+    fn next_permutation(p: &mut [usize]) -> bool {
+        let Some(i) = (1..p.len()).rev().find(|&i| p[i - 1] < p[i]) else { return false };
+        let j = (i..p.len()).rev().find(|&j| p[i - 1] < p[j]).unwrap();
+        p.swap(i - 1, j);
+        p[i..].reverse();
+        true
+    }
+
+    #[test]
+    fn test_topological_sort_against_brute_force() {
+        let mut rng = XorShift::default();
+        for _ in 0..300 {
+            let n = 1 + rng.next(6);
+            let m = rng.next(2 * n);
+            let edges: Vec<(usize, usize, i64)> = (0..m).map(|_| (rng.next(n), rng.next(n), rng.next(10) as i64)).collect();
+            let g = Graph::from_edges(n, &edges, true);
+
+            // 辞書順に全順列を調べて最初の有効な順序を探す
+            let mut perm: Vec<usize> = (0..n).collect();
+            let mut first = None;
+            loop {
+                if is_topological(&g, &perm) {
+                    first = Some(perm.clone());
+                    break;
+                }
+                if !next_permutation(&mut perm) {
+                    break;
+                }
+            }
+
+            match (topological_sort(&g), topological_sort_min(&g), first) {
+                (Ok(order), Ok(min_order), Some(expected)) => {
+                    assert!(is_topological(&g, &order));
+                    assert_eq!(min_order, expected);
+
+                    // 最長路とパスの個数を DFS の全探索と比較する
+                    let longest = longest_path(&g, |&w| w).unwrap();
+                    let s = rng.next(n);
+                    let counts = count_paths(&g, s, 7).unwrap();
+                    for v in 0..n {
+                        let mut best = 0;
+                        let mut paths = 0;
+                        let mut stack = vec![s];
+                        while let Some(u) = stack.pop() {
+                            paths += (u == v) as u64;
+                            stack.extend(g.neighbors(u).iter().map(|e| e.to));
+                        }
+                        let mut stack: Vec<(usize, i64)> = (0..n).map(|u| (u, 0)).collect();
+                        while let Some((u, len)) = stack.pop() {
+                            if u == v {
+                                best = best.max(len);
+                            }
+                            stack.extend(g.neighbors(u).iter().map(|e| (e.to, len + e.weight)));
+                        }
+                        assert_eq!(longest[v], best);
+                        assert_eq!(counts[v], paths % 7);
+                    }
+                }
+                (Err(Cycle(cycle)), Err(_), None) => {
+                    assert!(!cycle.is_empty());
+                    let mut sorted = cycle.clone();
+                    sorted.sort_unstable();
+                    sorted.dedup();
+                    assert_eq!(sorted.len(), cycle.len());
+                    for i in 0..cycle.len() {
+                        let (u, v) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+                        assert!(g.neighbors(u).iter().any(|e| e.to == v));
+                    }
+                }
+                _ => panic!("topological sort disagrees with brute force"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_self_loop_cycle() {
+        let g = Graph::from_edges(3, &[(0, 1, ()), (1, 1, ()), (1, 2, ())], true);
+        assert_eq!(topological_sort(&g), Err(Cycle(vec![1])));
+        let g = Graph::from_edges(3, &[(2, 0, ()), (2, 1, ())], true);
+        assert_eq!(topological_sort_min(&g), Ok(vec![2, 0, 1]));
+        assert_eq!(longest_path(&g, |_| 1), Ok(vec![1, 1, 0]));
+        assert_eq!(count_paths(&g, 2, 1_000_000_007), Ok(vec![1, 1, 1]));
+    }
+}
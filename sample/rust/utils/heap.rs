@@ -0,0 +1,400 @@
+// 左偏ヒープのノード
+struct LeftistNode<T> {
+    value: T,
+    // 右の背骨の長さ（null path length）
+    rank: usize,
+    left: Option<Box<LeftistNode<T>>>,
+    right: Option<Box<LeftistNode<T>>>,
+}
+
+// 左偏ヒープ（最小ヒープ、O(log n) でマージできる）
+// 「小さい方を大きい方にマージする」系の問題で BinaryHeap の代わりに使う
+pub struct LeftistHeap<T: Ord> {
+    root: Option<Box<LeftistNode<T>>>,
+    len: usize,
+}
+
+fn rank<T>(node: &Option<Box<LeftistNode<T>>>) -> usize {
+    node.as_ref().map_or(0, |n| n.rank)
+}
+
+// 2 つの左偏ヒープをマージする（再帰は右の背骨に沿うので深さ O(log n)）
+fn meld_nodes<T: Ord>(
+    a: Option<Box<LeftistNode<T>>>,
+    b: Option<Box<LeftistNode<T>>>,
+) -> Option<Box<LeftistNode<T>>> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.value > b.value {
+                std::mem::swap(&mut a, &mut b);
+            }
+            let right = a.right.take();
+            a.right = meld_nodes(right, Some(b));
+            if rank(&a.left) < rank(&a.right) {
+                std::mem::swap(&mut a.left, &mut a.right);
+            }
+            a.rank = rank(&a.right) + 1;
+            Some(a)
+        }
+    }
+}
+
+impl<T: Ord> LeftistHeap<T> {
+    pub fn new() -> Self {
+        LeftistHeap { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // 値を追加（O(log n)）
+    pub fn push(&mut self, value: T) {
+        let node = Box::new(LeftistNode { value, rank: 1, left: None, right: None });
+        self.root = meld_nodes(self.root.take(), Some(node));
+        self.len += 1;
+    }
+
+    // 最小値を参照
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|n| &n.value)
+    }
+
+    // 最小値を取り出す（O(log n)）
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let LeftistNode { value, left, right, .. } = *root;
+        self.root = meld_nodes(left, right);
+        self.len -= 1;
+        Some(value)
+    }
+
+    // other のすべての要素を取り込む（O(log n)）
+    pub fn meld(&mut self, mut other: LeftistHeap<T>) {
+        self.root = meld_nodes(self.root.take(), other.root.take());
+        self.len += other.len;
+        other.len = 0;
+    }
+}
+
+impl<T: Ord> Default for LeftistHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 左の背骨は O(n) の長さになり得るので、再帰しないように解放する
+impl<T: Ord> Drop for LeftistHeap<T> {
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<LeftistNode<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+// 添字付きヒープ（キー 0..n の最小ヒープ、decrease_key と任意要素の削除ができる）
+// 密グラフでの Dijkstra など、優先度を後から変更したい場合に使う
+pub struct IndexedHeap<K: Ord + Copy> {
+    // ヒープ上の id の並び
+    heap: Vec<usize>,
+    // id のヒープ上の位置（含まれていなければ usize::MAX）
+    pos: Vec<usize>,
+    keys: Vec<Option<K>>,
+}
+
+impl<K: Ord + Copy> IndexedHeap<K> {
+    // id が 0..n の範囲の空のヒープを作成
+    pub fn new(n: usize) -> Self {
+        IndexedHeap { heap: Vec::new(), pos: vec![usize::MAX; n], keys: vec![None; n] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    // id が含まれているか
+    pub fn contains(&self, id: usize) -> bool {
+        self.pos[id] != usize::MAX
+    }
+
+    // id のキー
+    pub fn key_of(&self, id: usize) -> Option<K> {
+        self.keys[id]
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.pos[self.heap[i]] = i;
+        self.pos[self.heap[j]] = j;
+    }
+
+    fn key_at(&self, i: usize) -> K {
+        self.keys[self.heap[i]].unwrap()
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.key_at(i) < self.key_at(parent) {
+                self.swap(i, parent);
+                i = parent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut smallest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.key_at(child) < self.key_at(smallest) {
+                    smallest = child;
+                }
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+
+    // id をキー key で追加、既にあればキーを変更する（O(log n)）
+    pub fn push(&mut self, id: usize, key: K) {
+        if self.contains(id) {
+            let old = self.keys[id].unwrap();
+            self.keys[id] = Some(key);
+            let i = self.pos[id];
+            if key < old {
+                self.sift_up(i);
+            } else {
+                self.sift_down(i);
+            }
+        } else {
+            self.keys[id] = Some(key);
+            self.heap.push(id);
+            self.pos[id] = self.heap.len() - 1;
+            self.sift_up(self.heap.len() - 1);
+        }
+    }
+
+    // id のキーを key に下げる（現在のキー以上なら何もせず false）
+    // id が含まれていなければ追加する
+    pub fn decrease_key(&mut self, id: usize, key: K) -> bool {
+        match self.keys[id] {
+            Some(old) if old <= key => false,
+            _ => {
+                self.push(id, key);
+                true
+            }
+        }
+    }
+
+    // 最小キーの (id, キー) を参照
+    pub fn peek(&self) -> Option<(usize, K)> {
+        self.heap.first().map(|&id| (id, self.keys[id].unwrap()))
+    }
+
+    // 最小キーの (id, キー) を取り出す
+    pub fn pop(&mut self) -> Option<(usize, K)> {
+        let id = *self.heap.first()?;
+        self.remove(id).map(|key| (id, key))
+    }
+
+    // id を削除し、そのキーを返す（O(log n)）
+    pub fn remove(&mut self, id: usize) -> Option<K> {
+        if !self.contains(id) {
+            return None;
+        }
+        let i = self.pos[id];
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        self.heap.pop();
+        self.pos[id] = usize::MAX;
+        if i < self.heap.len() {
+            self.sift_up(i);
+            self.sift_down(i);
+        }
+        self.keys[id].take()
+    }
+}
+
+// Radix Heap（取り出すキーが単調非減少な場合の u64 キーの最小ヒープ）
+// Dijkstra のように、最後に取り出したキー以上の値しか追加しない場合に使える
+pub struct RadixHeap<V> {
+    // buckets[i]: 最後に取り出したキーとの XOR の最上位ビットが i - 1 の要素
+    buckets: Vec<Vec<(u64, V)>>,
+    last: u64,
+    len: usize,
+}
+
+impl<V> RadixHeap<V> {
+    pub fn new() -> Self {
+        RadixHeap { buckets: (0..65).map(|_| Vec::new()).collect(), last: 0, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bucket(&self, key: u64) -> usize {
+        (64 - (key ^ self.last).leading_zeros()) as usize
+    }
+
+    // (key, value) を追加（key は最後に取り出したキー以上、O(1)）
+    pub fn push(&mut self, key: u64, value: V) {
+        assert!(key >= self.last, "key must not be less than the last popped key");
+        let b = self.bucket(key);
+        self.buckets[b].push((key, value));
+        self.len += 1;
+    }
+
+    // 最小キーの (key, value) を取り出す（償却 O(log C)）
+    pub fn pop(&mut self) -> Option<(u64, V)> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.buckets[0].is_empty() {
+            let i = (1..65).find(|&i| !self.buckets[i].is_empty()).unwrap();
+            let items = std::mem::take(&mut self.buckets[i]);
+            self.last = items.iter().map(|&(k, _)| k).min().unwrap();
+            for (key, value) in items {
+                let b = self.bucket(key);
+                self.buckets[b].push((key, value));
+            }
+        }
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+}
+
+impl<V> Default for RadixHeap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn test_leftist_heap_meld() {
+        let mut rng = XorShift::default();
+        let mut heaps: Vec<LeftistHeap<u64>> = (0..10).map(|_| LeftistHeap::new()).collect();
+        let mut naive: Vec<BinaryHeap<Reverse<u64>>> = (0..10).map(|_| BinaryHeap::new()).collect();
+        for _ in 0..5000 {
+            let i = rng.next(10);
+            match rng.next_u64() % 4 {
+                0 | 1 => {
+                    let x = rng.next_u64() % 1000;
+                    heaps[i].push(x);
+                    naive[i].push(Reverse(x));
+                }
+                2 => assert_eq!(heaps[i].pop(), naive[i].pop().map(|Reverse(x)| x)),
+                _ => {
+                    let j = rng.next(10);
+                    if i != j {
+                        let other = std::mem::take(&mut heaps[j]);
+                        heaps[i].meld(other);
+                        let other = std::mem::take(&mut naive[j]);
+                        naive[i].extend(other);
+                    }
+                }
+            }
+            assert_eq!(heaps[i].peek(), naive[i].peek().map(|Reverse(x)| x));
+            assert_eq!(heaps[i].len(), naive[i].len());
+        }
+    }
+
+    #[test]
+    fn test_leftist_heap_long_chain() {
+        // 降順に追加すると左の背骨が長くなる
+        let mut heap = LeftistHeap::new();
+        for x in (0..200000).rev() {
+            heap.push(x);
+        }
+        assert_eq!(heap.pop(), Some(0));
+    }
+
+    #[test]
+    fn test_indexed_heap() {
+        let mut rng = XorShift::new(2463534242);
+        let n = 50;
+        let mut heap = IndexedHeap::new(n);
+        let mut naive: Vec<Option<u64>> = vec![None; n];
+        for _ in 0..5000 {
+            let id = rng.next(n);
+            let key = rng.next_u64() % 100;
+            match rng.next_u64() % 4 {
+                0 => {
+                    heap.push(id, key);
+                    naive[id] = Some(key);
+                }
+                1 => {
+                    let changed = heap.decrease_key(id, key);
+                    let expected = !matches!(naive[id], Some(old) if old <= key);
+                    assert_eq!(changed, expected);
+                    if expected {
+                        naive[id] = Some(key);
+                    }
+                }
+                2 => assert_eq!(heap.remove(id), naive[id].take()),
+                _ => {
+                    let expected = (0..n).filter_map(|i| naive[i].map(|k| (k, i))).min();
+                    match heap.pop() {
+                        Some((i, k)) => {
+                            assert_eq!(Some(k), expected.map(|e| e.0));
+                            assert_eq!(naive[i], Some(k));
+                            naive[i] = None;
+                        }
+                        None => assert_eq!(expected, None),
+                    }
+                }
+            }
+            assert_eq!(heap.len(), naive.iter().filter(|k| k.is_some()).count());
+        }
+    }
+
+    #[test]
+    fn test_radix_heap() {
+        let mut rng = XorShift::new(123456789);
+        let mut heap = RadixHeap::new();
+        let mut naive = BinaryHeap::new();
+        let mut last = 0;
+        for i in 0..5000u64 {
+            // 2/3 の確率で追加、1/3 の確率で取り出す
+            if rng.next_u64().is_multiple_of(3) {
+                let popped = heap.pop();
+                assert_eq!(popped.map(|p| p.0), naive.pop().map(|Reverse(k)| k));
+                if let Some((key, _)) = popped {
+                    last = key;
+                }
+            } else {
+                let key = last + rng.next_u64() % 1000;
+                heap.push(key, i);
+                naive.push(Reverse(key));
+            }
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+use std::ops::{Add, Sub};
+
+// 互いに素な半開区間 [l, r) の集合
+// 隣接・重複する区間は挿入時にまとめ、削除時は区間を分割する
+pub struct RangeSet<T> {
+    // 区間の左端 -> 右端
+    ranges: BTreeMap<T, T>,
+    covered: T,
+}
+
+impl<T> RangeSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        RangeSet { ranges: BTreeMap::new(), covered: T::default() }
+    }
+
+    // 区間の個数
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // 覆われている長さの合計
+    pub fn covered_length(&self) -> T {
+        self.covered
+    }
+
+    // x を含む区間（なければ None）
+    pub fn covering(&self, x: T) -> Option<(T, T)> {
+        match self.ranges.range(..=x).next_back() {
+            Some((&l, &r)) if x < r => Some((l, r)),
+            _ => None,
+        }
+    }
+
+    // x が覆われているか
+    pub fn contains(&self, x: T) -> bool {
+        self.covering(x).is_some()
+    }
+
+    // x 以上で覆われていない最小の値
+    pub fn mex(&self, x: T) -> T {
+        match self.covering(x) {
+            Some((_, r)) => r,
+            None => x,
+        }
+    }
+
+    // [l, r) を追加し、新たに覆われた長さを返す（償却 O(log n)）
+    pub fn insert(&mut self, mut l: T, mut r: T) -> T {
+        if l >= r {
+            return T::default();
+        }
+        let before = self.covered;
+        // 左側で重なる・接する区間とまとめる
+        if let Some((&pl, &pr)) = self.ranges.range(..=l).next_back() {
+            if pr >= l {
+                if pr >= r {
+                    return T::default();
+                }
+                l = pl;
+            }
+        }
+        // 左端が [l, r] に入る区間をすべて取り込む
+        while let Some((&nl, &nr)) = self.ranges.range(l..).next() {
+            if nl > r {
+                break;
+            }
+            self.ranges.remove(&nl);
+            self.covered = self.covered - (nr - nl);
+            if nr > r {
+                r = nr;
+            }
+        }
+        self.ranges.insert(l, r);
+        self.covered = self.covered + (r - l);
+        self.covered - before
+    }
+
+    // [l, r) を削除し、削除された長さを返す（償却 O(log n)）
+    pub fn erase(&mut self, l: T, r: T) -> T {
+        if l >= r {
+            return T::default();
+        }
+        let before = self.covered;
+        // l をまたぐ区間を分割する
+        if let Some((&pl, &pr)) = self.ranges.range(..l).next_back() {
+            if pr > l {
+                self.ranges.insert(pl, l);
+                self.ranges.insert(l, pr);
+            }
+        }
+        while let Some((&nl, &nr)) = self.ranges.range(l..).next() {
+            if nl >= r {
+                break;
+            }
+            self.ranges.remove(&nl);
+            if nr > r {
+                self.ranges.insert(r, nr);
+                self.covered = self.covered - (r - nl);
+            } else {
+                self.covered = self.covered - (nr - nl);
+            }
+        }
+        before - self.covered
+    }
+
+    // 区間を左端の昇順に列挙する
+    pub fn iter(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.ranges.iter().map(|(&l, &r)| (l, r))
+    }
+}
+
+impl<T> Default for RangeSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 区間に値を割り当てるマップ（いわゆる ODT / Chtholly Tree）
+// 値が割り当てられた区間を互いに素に保ち、同じ値で隣接する区間はまとめる
+pub struct IntervalMap<K, V> {
+    // 区間の左端 -> (右端, 値)
+    pieces: BTreeMap<K, (K, V)>,
+}
+
+impl<K: Copy + Ord, V: Clone + PartialEq> IntervalMap<K, V> {
+    pub fn new() -> Self {
+        IntervalMap { pieces: BTreeMap::new() }
+    }
+
+    // 区間の個数
+    pub fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    // x をまたぐ区間を x の位置で 2 つに分ける
+    fn split(&mut self, x: K) {
+        let (l, r, v) = match self.pieces.range(..x).next_back() {
+            Some((&l, (r, v))) if x < *r => (l, *r, v.clone()),
+            _ => return,
+        };
+        self.pieces.insert(l, (x, v.clone()));
+        self.pieces.insert(x, (r, v));
+    }
+
+    // [l, r) の割り当てを取り除き、取り除いた区間を返す
+    pub fn erase(&mut self, l: K, r: K) -> Vec<(K, K, V)> {
+        if l >= r {
+            return vec![];
+        }
+        self.split(l);
+        self.split(r);
+        let keys: Vec<K> = self.pieces.range(l..r).map(|(&k, _)| k).collect();
+        keys.into_iter()
+            .map(|k| {
+                let (end, v) = self.pieces.remove(&k).unwrap();
+                (k, end, v)
+            })
+            .collect()
+    }
+
+    // [l, r) に値 v を割り当てる（償却 O(log n)）
+    pub fn assign(&mut self, mut l: K, mut r: K, v: V) {
+        if l >= r {
+            return;
+        }
+        self.erase(l, r);
+        // 同じ値で接している左右の区間とまとめる
+        if let Some((&pl, (pr, pv))) = self.pieces.range(..l).next_back() {
+            if *pr == l && *pv == v {
+                l = pl;
+                self.pieces.remove(&pl);
+            }
+        }
+        if let Some((nr, nv)) = self.pieces.get(&r) {
+            if *nv == v {
+                let nr = *nr;
+                self.pieces.remove(&r);
+                r = nr;
+            }
+        }
+        self.pieces.insert(l, (r, v));
+    }
+
+    // x に割り当てられた値
+    pub fn get(&self, x: K) -> Option<&V> {
+        match self.pieces.range(..=x).next_back() {
+            Some((_, (r, v))) if x < *r => Some(v),
+            _ => None,
+        }
+    }
+
+    // x を含む区間と値
+    pub fn get_range(&self, x: K) -> Option<(K, K, &V)> {
+        match self.pieces.range(..=x).next_back() {
+            Some((&l, (r, v))) if x < *r => Some((l, *r, v)),
+            _ => None,
+        }
+    }
+
+    // [l, r) と重なる区間を [l, r) に切り詰めて左から列挙する
+    pub fn range(&self, l: K, r: K) -> Vec<(K, K, &V)> {
+        let mut result = Vec::new();
+        if l >= r {
+            return result;
+        }
+        if let Some((pl, pr, v)) = self.get_range(l) {
+            if pl < l {
+                result.push((l, pr.min(r), v));
+            }
+        }
+        for (&nl, (nr, v)) in self.pieces.range(l..r) {
+            result.push((nl, (*nr).min(r), v));
+        }
+        result
+    }
+
+    // すべての区間を左端の昇順に列挙する
+    pub fn iter(&self) -> impl Iterator<Item = (K, K, &V)> + '_ {
+        self.pieces.iter().map(|(&l, (r, v))| (l, *r, v))
+    }
+}
+
+impl<K: Copy + Ord, V: Clone + PartialEq> Default for IntervalMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+
+    #[test]
+    fn test_range_set_against_array() {
+        const N: i64 = 60;
+        let mut rng = XorShift::default();
+        let mut set = RangeSet::new();
+        let mut covered = [false; N as usize + 1];
+        for _ in 0..2000 {
+            let l = rng.range(0, N);
+            let r = rng.range(l, N + 1);
+            let before = covered.iter().filter(|&&c| c).count() as i64;
+            if rng.next(2) == 0 {
+                let added = set.insert(l, r);
+                (l..r).for_each(|i| covered[i as usize] = true);
+                assert_eq!(added, covered.iter().filter(|&&c| c).count() as i64 - before);
+            } else {
+                let removed = set.erase(l, r);
+                (l..r).for_each(|i| covered[i as usize] = false);
+                assert_eq!(removed, before - covered.iter().filter(|&&c| c).count() as i64);
+            }
+            assert_eq!(set.covered_length(), covered.iter().filter(|&&c| c).count() as i64);
+            for x in 0..N {
+                assert_eq!(set.contains(x), covered[x as usize]);
+                let mex = (x..=N).find(|&y| !covered[y as usize]).unwrap();
+                assert_eq!(set.mex(x), mex);
+            }
+            // 区間は互いに素で、隣接もしていない
+            let ranges: Vec<(i64, i64)> = set.iter().collect();
+            assert!(ranges.windows(2).all(|w| w[0].1 < w[1].0));
+        }
+    }
+
+    #[test]
+    fn test_interval_map_against_array() {
+        const N: i64 = 40;
+        let mut rng = XorShift::new(2463534242);
+        let mut map = IntervalMap::new();
+        let mut values: Vec<Option<i64>> = vec![None; N as usize];
+        for _ in 0..2000 {
+            let l = rng.range(0, N);
+            let r = rng.range(l, N + 1);
+            if rng.next(4) == 0 {
+                map.erase(l, r);
+                (l..r).for_each(|i| values[i as usize] = None);
+            } else {
+                let v = rng.range(0, 3);
+                map.assign(l, r, v);
+                (l..r).for_each(|i| values[i as usize] = Some(v));
+            }
+            for x in 0..N {
+                assert_eq!(map.get(x).copied(), values[x as usize]);
+            }
+            let mut total = 0;
+            for (pl, pr, v) in map.range(l, r) {
+                assert!(l <= pl && pr <= r);
+                assert!((pl..pr).all(|i| values[i as usize] == Some(*v)));
+                total += pr - pl;
+            }
+            assert_eq!(total, (l..r).filter(|&i| values[i as usize].is_some()).count() as i64);
+            // 同じ値で隣接する区間はまとめられている
+            let pieces: Vec<(i64, i64, i64)> = map.iter().map(|(l, r, v)| (l, r, *v)).collect();
+            assert!(pieces.windows(2).all(|w| w[0].1 < w[1].0 || w[0].2 != w[1].2));
+        }
+    }
+}
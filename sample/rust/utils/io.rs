@@ -78,4 +78,51 @@ pub fn print_vec_lines<T: std::fmt::Display>(arr: &[T]) {
     for item in arr {
         println!("{}", item);
     }
-} 
\ No newline at end of file
+} 
+
+// 空白区切りのトークンを順に読み取るスキャナー（行をまたいだ入力にも対応）
+// グラフの辺など、まとまった入力を読み取るのに使う
+pub struct Scanner<R: BufRead> {
+    reader: R,
+    // 現在の行の未読トークン（逆順）
+    tokens: Vec<String>,
+}
+
+impl<R: BufRead> Scanner<R> {
+    pub fn new(reader: R) -> Self {
+        Scanner { reader, tokens: Vec::new() }
+    }
+
+    // 次のトークンを読み取って T に変換する
+    pub fn read<T: std::str::FromStr>(&mut self) -> T {
+        loop {
+            if let Some(token) = self.tokens.pop() {
+                return token.parse().ok().expect("failed to parse token");
+            }
+            let mut line = String::new();
+            let bytes = self.reader.read_line(&mut line).unwrap();
+            assert!(bytes > 0, "unexpected end of input");
+            self.tokens = line.split_whitespace().rev().map(|s| s.to_string()).collect();
+        }
+    }
+
+    // n 個のトークンを読み取る
+    pub fn read_vec<T: std::str::FromStr>(&mut self, n: usize) -> Vec<T> {
+        (0..n).map(|_| self.read()).collect()
+    }
+
+    // 1-indexed の頂点番号などを読み取って 0-indexed にする
+    pub fn read_usize1(&mut self) -> usize {
+        self.read::<usize>() - 1
+    }
+
+    // 次のトークンを文字のベクタで読み取る
+    pub fn read_chars(&mut self) -> Vec<char> {
+        self.read::<String>().chars().collect()
+    }
+}
+
+// 標準入力を読み取るスキャナーを作成
+pub fn stdin_scanner() -> Scanner<io::StdinLock<'static>> {
+    Scanner::new(io::stdin().lock())
+}
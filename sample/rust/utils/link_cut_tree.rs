@@ -0,0 +1,345 @@
+use super::data_structures::Monoid;
+
+const NIL: usize = usize::MAX;
+
+// Link-Cut Tree（辺の追加・削除がある森での連結判定とパス上の積）
+// splay 木で実装し、パスの向きを反転できるように逆順の積も持つ（非可換なモノイドに対応）
+pub struct LinkCutTree<M: Monoid> {
+    parent: Vec<usize>,
+    children: Vec<[usize; 2]>,
+    value: Vec<M::S>,
+    // splay 木の部分木の積（左から右の順と、その逆順）
+    sum: Vec<M::S>,
+    rev_sum: Vec<M::S>,
+    // 子の反転が未反映か
+    rev: Vec<bool>,
+}
+
+impl<M: Monoid> LinkCutTree<M> {
+    // 頂点 i の値を values[i] とする、辺のない森を作成
+    pub fn new(values: Vec<M::S>) -> Self {
+        let n = values.len();
+        LinkCutTree {
+            parent: vec![NIL; n],
+            children: vec![[NIL; 2]; n],
+            sum: values.clone(),
+            rev_sum: values.clone(),
+            value: values,
+            rev: vec![false; n],
+        }
+    }
+
+    // すべての頂点の値が単位元の森を作成
+    pub fn with_identity(n: usize) -> Self {
+        Self::new(vec![M::identity(); n])
+    }
+
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    // splay 木の根か（親がいないか、親からは path-parent でつながっている）
+    fn is_root(&self, x: usize) -> bool {
+        let p = self.parent[x];
+        p == NIL || (self.children[p][0] != x && self.children[p][1] != x)
+    }
+
+    fn sum_of(&self, x: usize) -> M::S {
+        if x == NIL {
+            M::identity()
+        } else {
+            self.sum[x].clone()
+        }
+    }
+
+    fn rev_sum_of(&self, x: usize) -> M::S {
+        if x == NIL {
+            M::identity()
+        } else {
+            self.rev_sum[x].clone()
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let [l, r] = self.children[x];
+        self.sum[x] = M::op(&M::op(&self.sum_of(l), &self.value[x]), &self.sum_of(r));
+        self.rev_sum[x] = M::op(&M::op(&self.rev_sum_of(r), &self.value[x]), &self.rev_sum_of(l));
+    }
+
+    // x の部分木を左右反転する（x 自身には即座に反映し、子には遅延させる）
+    fn toggle(&mut self, x: usize) {
+        if x == NIL {
+            return;
+        }
+        self.children[x].swap(0, 1);
+        std::mem::swap(&mut self.sum[x], &mut self.rev_sum[x]);
+        self.rev[x] ^= true;
+    }
+
+    fn push(&mut self, x: usize) {
+        if self.rev[x] {
+            let [l, r] = self.children[x];
+            self.toggle(l);
+            self.toggle(r);
+            self.rev[x] = false;
+        }
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.parent[x];
+        let g = self.parent[p];
+        let dir = if self.children[p][0] == x { 0 } else { 1 };
+        let b = self.children[x][dir ^ 1];
+        if !self.is_root(p) {
+            let pdir = if self.children[g][0] == p { 0 } else { 1 };
+            self.children[g][pdir] = x;
+        }
+        self.parent[x] = g;
+        self.children[x][dir ^ 1] = p;
+        self.parent[p] = x;
+        self.children[p][dir] = b;
+        if b != NIL {
+            self.parent[b] = p;
+        }
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        // 上から順に遅延していた反転を反映する
+        let mut path = vec![x];
+        let mut y = x;
+        while !self.is_root(y) {
+            y = self.parent[y];
+            path.push(y);
+        }
+        while let Some(y) = path.pop() {
+            self.push(y);
+        }
+        while !self.is_root(x) {
+            let p = self.parent[x];
+            if !self.is_root(p) {
+                let g = self.parent[p];
+                let zig_zig = (self.children[g][0] == p) == (self.children[p][0] == x);
+                self.rotate(if zig_zig { p } else { x });
+            }
+            self.rotate(x);
+        }
+    }
+
+    // 根から x までのパスを 1 本の splay 木にし、x をその根にする
+    // 返り値は最後に path-parent をたどった頂点（lca の計算に使う）
+    fn expose(&mut self, x: usize) -> usize {
+        let mut last = NIL;
+        let mut y = x;
+        while y != NIL {
+            self.splay(y);
+            self.children[y][1] = last;
+            self.update(y);
+            last = y;
+            y = self.parent[y];
+        }
+        self.splay(x);
+        last
+    }
+
+    // x を木の根にする
+    pub fn evert(&mut self, x: usize) {
+        self.expose(x);
+        self.toggle(x);
+        self.push(x);
+    }
+
+    // x を含む木の根
+    pub fn root(&mut self, x: usize) -> usize {
+        self.expose(x);
+        let mut y = x;
+        loop {
+            self.push(y);
+            let l = self.children[y][0];
+            if l == NIL {
+                break;
+            }
+            y = l;
+        }
+        self.splay(y);
+        y
+    }
+
+    // u と v が同じ木に属するか
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        u == v || self.root(u) == self.root(v)
+    }
+
+    // 辺 (u, v) を追加する（u と v は別の木に属すること）
+    pub fn link(&mut self, u: usize, v: usize) {
+        assert!(!self.connected(u, v), "{} and {} are already connected", u, v);
+        self.evert(u);
+        self.parent[u] = v;
+    }
+
+    // 辺 (u, v) を削除する（辺がなければ false）
+    // 元の根を含む側の根は変わらず、切り離された側は u か v が根になる
+    pub fn cut(&mut self, u: usize, v: usize) -> bool {
+        if u == v {
+            return false;
+        }
+        let root = self.root(u);
+        self.evert(u);
+        self.expose(v);
+        // 辺があれば、v の splay 木の左側は u だけになっている
+        let found = self.children[v][0] == u && self.children[u][1] == NIL;
+        if found {
+            self.children[v][0] = NIL;
+            self.parent[u] = NIL;
+            self.update(v);
+        }
+        self.evert(root);
+        found
+    }
+
+    // 現在の根に関する u と v の最小共通祖先（別の木なら None）
+    pub fn lca(&mut self, u: usize, v: usize) -> Option<usize> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.expose(u);
+        Some(self.expose(v))
+    }
+
+    // u から v へのパス上の頂点の値を順に掛けた積（別の木なら None）
+    // 根が u に変わる
+    pub fn path_fold(&mut self, u: usize, v: usize) -> Option<M::S> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.evert(u);
+        self.expose(v);
+        Some(self.sum[v].clone())
+    }
+
+    // 頂点 x の値を取得
+    pub fn get(&self, x: usize) -> M::S {
+        self.value[x].clone()
+    }
+
+    // 頂点 x の値を変更する
+    pub fn set(&mut self, x: usize, value: M::S) {
+        self.expose(x);
+        self.value[x] = value;
+        self.update(x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::{path_to_root, Affine, XorShift};
+    use std::collections::VecDeque;
+
+    // 愚直な森: BFS で u から v へのパスを求める
+    fn naive_path(adj: &[Vec<usize>], u: usize, v: usize) -> Option<Vec<usize>> {
+        let mut prev = vec![NIL; adj.len()];
+        let mut queue = VecDeque::from(vec![u]);
+        prev[u] = u;
+        while let Some(x) = queue.pop_front() {
+            for &y in &adj[x] {
+                if prev[y] == NIL {
+                    prev[y] = x;
+                    queue.push_back(y);
+                }
+            }
+        }
+        if prev[v] == NIL {
+            return None;
+        }
+        let mut path = vec![v];
+        while *path.last().unwrap() != u {
+            path.push(prev[*path.last().unwrap()]);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    #[test]
+    fn test_link_cut_tree_against_naive() {
+        let n = 30;
+        let mut rng = XorShift::default();
+        let mut values: Vec<(u64, u64)> = (0..n).map(|_| (rng.next(10) as u64 + 1, rng.next(10) as u64)).collect();
+        let mut lct: LinkCutTree<Affine> = LinkCutTree::new(values.clone());
+        let mut adj: Vec<Vec<usize>> = vec![vec![]; n];
+        for _ in 0..5000 {
+            let (u, v) = (rng.next(n), rng.next(n));
+            match rng.next(5) {
+                0 => {
+                    if u != v && naive_path(&adj, u, v).is_none() {
+                        lct.link(u, v);
+                        adj[u].push(v);
+                        adj[v].push(u);
+                    }
+                }
+                1 => {
+                    let has_edge = adj[u].contains(&v);
+                    assert_eq!(lct.cut(u, v), has_edge);
+                    if has_edge {
+                        adj[u].retain(|&x| x != v);
+                        adj[v].retain(|&x| x != u);
+                    }
+                }
+                2 => {
+                    let x = (rng.next(10) as u64 + 1, rng.next(10) as u64);
+                    lct.set(u, x);
+                    values[u] = x;
+                }
+                _ => {
+                    let path = naive_path(&adj, u, v);
+                    assert_eq!(lct.connected(u, v), path.is_some());
+                    let expected = path.map(|p| p.iter().fold(Affine::identity(), |acc, &x| Affine::op(&acc, &values[x])));
+                    assert_eq!(lct.path_fold(u, v), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_link_cut_tree_lca() {
+        // 0 を根とする木: 親は parent[i] < i
+        let n = 50;
+        let mut rng = XorShift::new(2463534242);
+        let mut parent: Vec<usize> = (0..n).map(|i| if i == 0 { NIL } else { rng.next(i) }).collect();
+        let mut lct: LinkCutTree<Affine> = LinkCutTree::with_identity(n);
+        for (i, &p) in parent.iter().enumerate().skip(1) {
+            lct.link(i, p);
+        }
+        let check = |lct: &mut LinkCutTree<Affine>, parent: &[usize], rng: &mut XorShift| {
+            for _ in 0..500 {
+                let (u, v) = (rng.next(n), rng.next(n));
+                let pu = path_to_root(parent, u);
+                let expected = path_to_root(parent, v).into_iter().find(|x| pu.contains(x));
+                assert_eq!(lct.lca(u, v), expected);
+            }
+        };
+        check(&mut lct, &parent, &mut rng);
+        assert_eq!(lct.root(n - 1), 0);
+
+        // cut の後も元の根は変わらず、切り離された側は子の側の端点が根になる
+        for _ in 0..10 {
+            let c = 1 + rng.next(n - 1);
+            if parent[c] == NIL {
+                continue;
+            }
+            let p = parent[c];
+            let cut = if rng.next(2) == 0 { lct.cut(c, p) } else { lct.cut(p, c) };
+            assert!(cut);
+            parent[c] = NIL;
+            assert_eq!(lct.root(p), path_to_root(&parent, p).pop().unwrap());
+            assert_eq!(lct.root(c), c);
+            check(&mut lct, &parent, &mut rng);
+        }
+        assert!(!lct.cut(0, 0));
+    }
+}
@@ -0,0 +1,362 @@
+// Mo's algorithm で管理する区間の状態
+// 区間 [l, r) の端を 1 つずつ伸縮させながら答えを計算する
+pub trait MoState {
+    type Answer;
+
+    // 区間の左端に要素 i を追加（[i + 1, r) -> [i, r)）
+    fn add_left(&mut self, i: usize);
+    // 区間の右端に要素 i を追加（[l, i) -> [l, i + 1)）
+    fn add_right(&mut self, i: usize);
+    // 区間の左端の要素 i を削除（[i, r) -> [i + 1, r)）
+    fn remove_left(&mut self, i: usize);
+    // 区間の右端の要素 i を削除（[l, i + 1) -> [l, i)）
+    fn remove_right(&mut self, i: usize);
+    // 現在の区間に対する答え
+    fn answer(&self) -> Self::Answer;
+}
+
+// クエリの並べ方
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoOrder {
+    // ヒルベルト曲線順（多くの場合で最速）
+    Hilbert,
+    // 平方分割 + 奇数ブロックで r を逆順にする並べ方
+    OddEvenBlock,
+}
+
+// Mo's algorithm（静的配列に対するオフライン区間クエリ）
+// クエリはすべて半開区間 [l, r)、答えは入力順に返す
+pub struct Mo {
+    n: usize,
+    queries: Vec<(usize, usize)>,
+    order: MoOrder,
+}
+
+// (x, y) のヒルベルト曲線上の位置（座標は 2^log 未満）
+fn hilbert_order(mut x: u64, mut y: u64, log: u32) -> u64 {
+    let n = 1u64 << log;
+    let mut d = 0;
+    let mut s = n >> 1;
+    while s > 0 {
+        let rx = (x & s) > 0;
+        let ry = (y & s) > 0;
+        d += s * s * ((3 * rx as u64) ^ ry as u64);
+        if !ry {
+            if rx {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s >>= 1;
+    }
+    d
+}
+
+impl Mo {
+    // 長さ n の配列に対するクエリ列から作成（ヒルベルト曲線順）
+    pub fn new(n: usize, queries: Vec<(usize, usize)>) -> Self {
+        Self::with_order(n, queries, MoOrder::Hilbert)
+    }
+
+    // クエリの並べ方を指定して作成
+    pub fn with_order(n: usize, queries: Vec<(usize, usize)>, order: MoOrder) -> Self {
+        for &(l, r) in &queries {
+            assert!(l <= r && r <= n, "query [{}, {}) is out of range", l, r);
+        }
+        Mo { n, queries, order }
+    }
+
+    // クエリを処理する順番
+    fn sorted_indices(&self) -> Vec<usize> {
+        let q = self.queries.len();
+        let mut indices: Vec<usize> = (0..q).collect();
+        match self.order {
+            MoOrder::Hilbert => {
+                let mut log = 0;
+                while (1usize << log) <= self.n {
+                    log += 1;
+                }
+                let keys: Vec<u64> = self
+                    .queries
+                    .iter()
+                    .map(|&(l, r)| hilbert_order(l as u64, r as u64, log))
+                    .collect();
+                indices.sort_by_key(|&i| keys[i]);
+            }
+            MoOrder::OddEvenBlock => {
+                let block = (self.n / ((q as f64).sqrt() as usize).max(1)).max(1);
+                indices.sort_by_key(|&i| {
+                    let (l, r) = self.queries[i];
+                    let b = l / block;
+                    if b & 1 == 0 {
+                        (b, r)
+                    } else {
+                        (b, self.n - r)
+                    }
+                });
+            }
+        }
+        indices
+    }
+
+    // 全クエリを処理して、入力順に答えを返す（O((n + q) sqrt(q)) 回の追加・削除）
+    pub fn run<S: MoState>(&self, state: &mut S) -> Vec<S::Answer> {
+        let mut answers: Vec<Option<S::Answer>> = (0..self.queries.len()).map(|_| None).collect();
+        let (mut cl, mut cr) = (0, 0);
+        for i in self.sorted_indices() {
+            let (l, r) = self.queries[i];
+            // 先に区間を広げてから縮める（区間が負の長さにならないようにする）
+            while cl > l {
+                cl -= 1;
+                state.add_left(cl);
+            }
+            while cr < r {
+                state.add_right(cr);
+                cr += 1;
+            }
+            while cl < l {
+                state.remove_left(cl);
+                cl += 1;
+            }
+            while cr > r {
+                cr -= 1;
+                state.remove_right(cr);
+            }
+            answers[i] = Some(state.answer());
+        }
+        answers.into_iter().map(|a| a.unwrap()).collect()
+    }
+}
+
+// 削除できない状態（最大値、巻き戻し付きの種類数など）のための Mo の状態
+// 追加と、snapshot 時点への巻き戻しだけを実装すればよい
+pub trait RollbackMoState {
+    type Answer;
+
+    // 区間の左端に要素 i を追加
+    fn add_left(&mut self, i: usize);
+    // 区間の右端に要素 i を追加
+    fn add_right(&mut self, i: usize);
+    // 現在の状態を記録する
+    fn snapshot(&mut self);
+    // 直前の snapshot の状態に戻す
+    fn rollback(&mut self);
+    // 空区間の状態に戻す
+    fn reset(&mut self);
+    // 現在の区間に対する答え
+    fn answer(&self) -> Self::Answer;
+}
+
+// 巻き戻し Mo（追加のみで削除を使わない Mo's algorithm）
+pub struct RollbackMo {
+    n: usize,
+    queries: Vec<(usize, usize)>,
+}
+
+impl RollbackMo {
+    // 長さ n の配列に対するクエリ列（半開区間 [l, r)）から作成
+    pub fn new(n: usize, queries: Vec<(usize, usize)>) -> Self {
+        for &(l, r) in &queries {
+            assert!(l <= r && r <= n, "query [{}, {}) is out of range", l, r);
+        }
+        RollbackMo { n, queries }
+    }
+
+    // 全クエリを処理して、入力順に答えを返す
+    pub fn run<S: RollbackMoState>(&self, state: &mut S) -> Vec<S::Answer> {
+        let q = self.queries.len();
+        let mut answers: Vec<Option<S::Answer>> = (0..q).map(|_| None).collect();
+        if q == 0 {
+            return vec![];
+        }
+        let block = (self.n / ((q as f64).sqrt() as usize).max(1)).max(1);
+        let mut indices: Vec<usize> = (0..q).collect();
+        indices.sort_by_key(|&i| (self.queries[i].0 / block, self.queries[i].1));
+
+        let mut pos = 0;
+        while pos < q {
+            let b = self.queries[indices[pos]].0 / block;
+            let boundary = ((b + 1) * block).min(self.n);
+            state.reset();
+            let mut cr = boundary;
+            while pos < q && self.queries[indices[pos]].0 / block == b {
+                let i = indices[pos];
+                let (l, r) = self.queries[i];
+                if r <= boundary {
+                    // ブロック内に収まるクエリは愚直に計算する（r でソート済みなので状態は空）
+                    state.snapshot();
+                    for j in l..r {
+                        state.add_right(j);
+                    }
+                    answers[i] = Some(state.answer());
+                    state.rollback();
+                } else {
+                    while cr < r {
+                        state.add_right(cr);
+                        cr += 1;
+                    }
+                    state.snapshot();
+                    for j in (l..boundary).rev() {
+                        state.add_left(j);
+                    }
+                    answers[i] = Some(state.answer());
+                    state.rollback();
+                }
+                pos += 1;
+            }
+        }
+        answers.into_iter().map(|a| a.unwrap()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+
+    // 区間内の異なる値の個数
+    struct Distinct<'a> {
+        a: &'a [usize],
+        count: Vec<usize>,
+        distinct: usize,
+    }
+
+    impl Distinct<'_> {
+        fn add(&mut self, i: usize) {
+            self.count[self.a[i]] += 1;
+            if self.count[self.a[i]] == 1 {
+                self.distinct += 1;
+            }
+        }
+
+        fn remove(&mut self, i: usize) {
+            self.count[self.a[i]] -= 1;
+            if self.count[self.a[i]] == 0 {
+                self.distinct -= 1;
+            }
+        }
+    }
+
+    impl MoState for Distinct<'_> {
+        type Answer = usize;
+
+        fn add_left(&mut self, i: usize) {
+            self.add(i);
+        }
+        fn add_right(&mut self, i: usize) {
+            self.add(i);
+        }
+        fn remove_left(&mut self, i: usize) {
+            self.remove(i);
+        }
+        fn remove_right(&mut self, i: usize) {
+            self.remove(i);
+        }
+        fn answer(&self) -> usize {
+            self.distinct
+        }
+    }
+
+    // 区間内の (値 × 出現回数) の最大値（削除が難しい例）
+    struct MaxImportance<'a> {
+        a: &'a [usize],
+        count: Vec<usize>,
+        best: usize,
+        history: Vec<(usize, usize)>,
+        saved: Vec<(usize, usize)>,
+    }
+
+    impl MaxImportance<'_> {
+        fn add(&mut self, i: usize) {
+            let v = self.a[i];
+            self.history.push((v, self.best));
+            self.count[v] += 1;
+            self.best = self.best.max(v * self.count[v]);
+        }
+    }
+
+    impl RollbackMoState for MaxImportance<'_> {
+        type Answer = usize;
+
+        fn add_left(&mut self, i: usize) {
+            self.add(i);
+        }
+        fn add_right(&mut self, i: usize) {
+            self.add(i);
+        }
+        fn snapshot(&mut self) {
+            self.saved.push((self.history.len(), self.best));
+        }
+        fn rollback(&mut self) {
+            let (len, best) = self.saved.pop().unwrap();
+            while self.history.len() > len {
+                let (v, _) = self.history.pop().unwrap();
+                self.count[v] -= 1;
+            }
+            self.best = best;
+        }
+        fn reset(&mut self) {
+            self.count.iter_mut().for_each(|c| *c = 0);
+            self.history.clear();
+            self.saved.clear();
+            self.best = 0;
+        }
+        fn answer(&self) -> usize {
+            self.best
+        }
+    }
+
+    fn random_input() -> (Vec<usize>, Vec<(usize, usize)>) {
+        let mut rng = XorShift::default();
+        let n = 200;
+        let a: Vec<usize> = (0..n).map(|_| rng.next(20)).collect();
+        let queries = (0..300)
+            .map(|_| {
+                let l = rng.next(n + 1);
+                let r = l + rng.next(n + 1 - l);
+                (l, r)
+            })
+            .collect();
+        (a, queries)
+    }
+
+    #[test]
+    fn test_mo_distinct_count() {
+        let (a, queries) = random_input();
+        let expected: Vec<usize> = queries
+            .iter()
+            .map(|&(l, r)| {
+                let mut v = a[l..r].to_vec();
+                v.sort();
+                v.dedup();
+                v.len()
+            })
+            .collect();
+        for order in [MoOrder::Hilbert, MoOrder::OddEvenBlock] {
+            let mo = Mo::with_order(a.len(), queries.clone(), order);
+            let mut state = Distinct { a: &a, count: vec![0; 20], distinct: 0 };
+            assert_eq!(mo.run(&mut state), expected);
+        }
+    }
+
+    #[test]
+    fn test_rollback_mo() {
+        let (a, queries) = random_input();
+        let expected: Vec<usize> = queries
+            .iter()
+            .map(|&(l, r)| {
+                let mut count = [0; 20];
+                let mut best = 0;
+                for &v in &a[l..r] {
+                    count[v] += 1;
+                    best = best.max(v * count[v]);
+                }
+                best
+            })
+            .collect();
+        let mo = RollbackMo::new(a.len(), queries);
+        let mut state = MaxImportance { a: &a, count: vec![0; 20], best: 0, history: vec![], saved: vec![] };
+        assert_eq!(mo.run(&mut state), expected);
+    }
+}
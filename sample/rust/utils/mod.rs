@@ -5,4 +5,17 @@ pub mod math;
 pub mod string;
 pub mod bit;
 pub mod data_structures;
-pub mod search; 
\ No newline at end of file
+pub mod search;
+pub mod dp;
+pub mod mo;
+pub mod sliding_window;
+pub mod interval;
+pub mod heap;
+pub mod segment_tree;
+pub mod sqrt_decomposition;
+pub mod link_cut_tree;
+pub mod prefix_sum;
+pub mod rope;
+pub mod graph;
+#[cfg(test)]
+pub(crate) mod test_util;
@@ -0,0 +1,261 @@
+use std::ops::{Add, Sub};
+
+// 累積和（区間和を O(1) で求める、区間は半開区間 [l, r)）
+// T は i64 や ModInt など、0 を Default とする加減算のできる型
+pub struct PrefixSum<T> {
+    prefix: Vec<T>,
+}
+
+impl<T> PrefixSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    // 配列から累積和を構築（O(n)）
+    pub fn new(arr: &[T]) -> Self {
+        let mut prefix = Vec::with_capacity(arr.len() + 1);
+        prefix.push(T::default());
+        for (i, &x) in arr.iter().enumerate() {
+            prefix.push(prefix[i] + x);
+        }
+        PrefixSum { prefix }
+    }
+
+    // 元の配列の長さ
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // [l, r) の和
+    pub fn sum(&self, l: usize, r: usize) -> T {
+        assert!(l <= r && r <= self.len());
+        self.prefix[r] - self.prefix[l]
+    }
+
+    // 先頭から r 個の和（[0, r) の和）
+    pub fn prefix(&self, r: usize) -> T {
+        self.prefix[r]
+    }
+}
+
+// いもす法（区間加算をまとめて行い、最後に配列を復元する）
+pub struct Imos<T> {
+    diff: Vec<T>,
+}
+
+impl<T> Imos<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    // 長さ n のすべて 0 の配列
+    pub fn new(n: usize) -> Self {
+        Imos { diff: vec![T::default(); n + 1] }
+    }
+
+    // [l, r) に x を加える（O(1)）
+    pub fn add(&mut self, l: usize, r: usize, x: T) {
+        assert!(l <= r && r < self.diff.len());
+        self.diff[l] = self.diff[l] + x;
+        self.diff[r] = self.diff[r] - x;
+    }
+
+    // 加算をすべて反映した配列を返す（O(n)）
+    pub fn build(&self) -> Vec<T> {
+        let n = self.diff.len() - 1;
+        let mut result = Vec::with_capacity(n);
+        let mut acc = T::default();
+        for &d in &self.diff[..n] {
+            acc = acc + d;
+            result.push(acc);
+        }
+        result
+    }
+}
+
+// 2 次元累積和（長方形領域の和を O(1) で求める）
+pub struct PrefixSum2D<T> {
+    prefix: Vec<Vec<T>>,
+}
+
+impl<T> PrefixSum2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    // h x w のグリッドから構築（O(hw)）
+    pub fn new(grid: &[Vec<T>]) -> Self {
+        let h = grid.len();
+        let w = if h > 0 { grid[0].len() } else { 0 };
+        let mut prefix = vec![vec![T::default(); w + 1]; h + 1];
+        for i in 0..h {
+            assert_eq!(grid[i].len(), w);
+            for j in 0..w {
+                prefix[i + 1][j + 1] = prefix[i][j + 1] + prefix[i + 1][j] - prefix[i][j] + grid[i][j];
+            }
+        }
+        PrefixSum2D { prefix }
+    }
+
+    // 行 [r1, r2)、列 [c1, c2) の長方形領域の和
+    pub fn sum(&self, r1: usize, c1: usize, r2: usize, c2: usize) -> T {
+        assert!(r1 <= r2 && c1 <= c2);
+        self.prefix[r2][c2] - self.prefix[r1][c2] - self.prefix[r2][c1] + self.prefix[r1][c1]
+    }
+}
+
+// 2 次元いもす法（長方形領域への加算をまとめて行い、最後にグリッドを復元する）
+pub struct Difference2D<T> {
+    diff: Vec<Vec<T>>,
+    h: usize,
+    w: usize,
+}
+
+impl<T> Difference2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    // h x w のすべて 0 のグリッド
+    pub fn new(h: usize, w: usize) -> Self {
+        Difference2D { diff: vec![vec![T::default(); w + 1]; h + 1], h, w }
+    }
+
+    // 行 [r1, r2)、列 [c1, c2) の長方形領域に x を加える（O(1)）
+    pub fn add(&mut self, r1: usize, c1: usize, r2: usize, c2: usize, x: T) {
+        assert!(r1 <= r2 && r2 <= self.h && c1 <= c2 && c2 <= self.w);
+        self.diff[r1][c1] = self.diff[r1][c1] + x;
+        self.diff[r1][c2] = self.diff[r1][c2] - x;
+        self.diff[r2][c1] = self.diff[r2][c1] - x;
+        self.diff[r2][c2] = self.diff[r2][c2] + x;
+    }
+
+    // 加算をすべて反映したグリッドを返す（O(hw)）
+    pub fn build(&self) -> Vec<Vec<T>> {
+        let mut grid = Vec::with_capacity(self.h);
+        // 直前の行までの累積（1 行目の上は 0）
+        let mut above = vec![T::default(); self.w];
+        for diff_row in &self.diff[..self.h] {
+            let mut row = T::default();
+            for (cell, &d) in above.iter_mut().zip(diff_row) {
+                row = row + d;
+                *cell = *cell + row;
+            }
+            grid.push(above.clone());
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+
+    #[test]
+    fn test_prefix_sum_and_imos() {
+        let arr: Vec<i64> = vec![3, -1, 4, 1, -5, 9, 2, -6];
+        let ps = PrefixSum::new(&arr);
+        for l in 0..=arr.len() {
+            for r in l..=arr.len() {
+                assert_eq!(ps.sum(l, r), arr[l..r].iter().sum::<i64>());
+            }
+        }
+
+        let mut imos = Imos::new(8);
+        let mut naive = vec![0i64; 8];
+        for (l, r, x) in [(0, 3, 5), (2, 8, -2), (4, 4, 100), (7, 8, 1)] {
+            imos.add(l, r, x);
+            naive[l..r].iter_mut().for_each(|v| *v += x);
+        }
+        assert_eq!(imos.build(), naive);
+    }
+
+    #[test]
+    fn test_prefix_sum_2d_and_difference_2d() {
+        let (h, w) = (5, 7);
+        let grid: Vec<Vec<i64>> = (0..h).map(|i| (0..w).map(|j| (i * 7 + j * 3) as i64 % 11 - 5).collect()).collect();
+        let ps = PrefixSum2D::new(&grid);
+        for r1 in 0..=h {
+            for r2 in r1..=h {
+                for c1 in 0..=w {
+                    for c2 in c1..=w {
+                        let expected: i64 = (r1..r2).map(|i| grid[i][c1..c2].iter().sum::<i64>()).sum();
+                        assert_eq!(ps.sum(r1, c1, r2, c2), expected);
+                    }
+                }
+            }
+        }
+
+        let mut diff = Difference2D::new(h, w);
+        let mut naive = vec![vec![0i64; w]; h];
+        for (r1, c1, r2, c2, x) in [(0, 0, 5, 7, 1), (1, 2, 3, 6, 10), (4, 0, 5, 1, -3), (2, 2, 2, 5, 7)] {
+            diff.add(r1, c1, r2, c2, x);
+            for row in naive.iter_mut().take(r2).skip(r1) {
+                row[c1..c2].iter_mut().for_each(|v| *v += x);
+            }
+        }
+        assert_eq!(diff.build(), naive);
+    }
+
+    const MOD: u64 = 998244353;
+
+    // mod p の値（ModInt の代わりに、加減算だけを持つ最小限の型）
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    struct ModP(u64);
+
+    impl Add for ModP {
+        type Output = ModP;
+        fn add(self, rhs: ModP) -> ModP {
+            ModP((self.0 + rhs.0) % MOD)
+        }
+    }
+
+    impl Sub for ModP {
+        type Output = ModP;
+        fn sub(self, rhs: ModP) -> ModP {
+            ModP((self.0 + MOD - rhs.0) % MOD)
+        }
+    }
+
+    #[test]
+    fn test_mod_p_values() {
+        let mut rng = XorShift::default();
+        let arr: Vec<u64> = (0..50).map(|_| rng.next_u64() % MOD).collect();
+        let ps = PrefixSum::new(&arr.iter().map(|&x| ModP(x)).collect::<Vec<_>>());
+        for l in 0..=arr.len() {
+            for r in l..=arr.len() {
+                assert_eq!(ps.sum(l, r), ModP(arr[l..r].iter().sum::<u64>() % MOD));
+            }
+        }
+
+        let n = 20;
+        let mut imos = Imos::new(n);
+        let mut naive = vec![0u64; n];
+        for _ in 0..100 {
+            let l = rng.next(n + 1);
+            let r = l + rng.next(n + 1 - l);
+            let x = rng.next_u64() % MOD;
+            imos.add(l, r, ModP(x));
+            naive[l..r].iter_mut().for_each(|v| *v = (*v + x) % MOD);
+        }
+        assert_eq!(imos.build(), naive.iter().map(|&v| ModP(v)).collect::<Vec<_>>());
+
+        let (h, w) = (6, 5);
+        let mut diff = Difference2D::new(h, w);
+        let mut naive = vec![vec![0u64; w]; h];
+        for _ in 0..100 {
+            let r1 = rng.next(h + 1);
+            let r2 = r1 + rng.next(h + 1 - r1);
+            let c1 = rng.next(w + 1);
+            let c2 = c1 + rng.next(w + 1 - c1);
+            let x = rng.next_u64() % MOD;
+            diff.add(r1, c1, r2, c2, ModP(x));
+            for row in naive.iter_mut().take(r2).skip(r1) {
+                row[c1..c2].iter_mut().for_each(|v| *v = (*v + x) % MOD);
+            }
+        }
+        let expected: Vec<Vec<ModP>> = naive.iter().map(|row| row.iter().map(|&v| ModP(v)).collect()).collect();
+        assert_eq!(diff.build(), expected);
+    }
+}
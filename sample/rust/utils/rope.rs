@@ -0,0 +1,339 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// 暗黙の treap のノード
+struct RopeNode<T> {
+    value: T,
+    priority: u64,
+    size: usize,
+    // 子の左右反転が未反映か
+    rev: bool,
+    left: Option<Box<RopeNode<T>>>,
+    right: Option<Box<RopeNode<T>>>,
+}
+
+type Link<T> = Option<Box<RopeNode<T>>>;
+
+// treap の優先度（splitmix64 で生成する）
+fn next_priority() -> u64 {
+    static STATE: AtomicU64 = AtomicU64::new(0x2545_f491_4f6c_dd1d);
+    let mut z = STATE.fetch_add(0x9e37_79b9_7f4a_7c15, Ordering::Relaxed);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn size<T>(node: &Link<T>) -> usize {
+    node.as_ref().map_or(0, |n| n.size)
+}
+
+fn update<T>(node: &mut RopeNode<T>) {
+    node.size = size(&node.left) + size(&node.right) + 1;
+}
+
+fn push<T>(node: &mut RopeNode<T>) {
+    if node.rev {
+        std::mem::swap(&mut node.left, &mut node.right);
+        if let Some(l) = node.left.as_mut() {
+            l.rev ^= true;
+        }
+        if let Some(r) = node.right.as_mut() {
+            r.rev ^= true;
+        }
+        node.rev = false;
+    }
+}
+
+// 先頭 k 個とそれ以外に分ける（期待 O(log n)）
+fn split<T>(node: Link<T>, k: usize) -> (Link<T>, Link<T>) {
+    match node {
+        None => (None, None),
+        Some(mut n) => {
+            push(&mut n);
+            if size(&n.left) >= k {
+                let (l, r) = split(n.left.take(), k);
+                n.left = r;
+                update(&mut n);
+                (l, Some(n))
+            } else {
+                let k = k - size(&n.left) - 1;
+                let (l, r) = split(n.right.take(), k);
+                n.right = l;
+                update(&mut n);
+                (Some(n), r)
+            }
+        }
+    }
+}
+
+// a の後ろに b をつなげる（期待 O(log n)）
+fn merge<T>(a: Link<T>, b: Link<T>) -> Link<T> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.priority > b.priority {
+                push(&mut a);
+                a.right = merge(a.right.take(), Some(b));
+                update(&mut a);
+                Some(a)
+            } else {
+                push(&mut b);
+                b.left = merge(Some(a), b.left.take());
+                update(&mut b);
+                Some(b)
+            }
+        }
+    }
+}
+
+// node 以下の要素を中順に result へ追加する（反転フラグの偶奇をたどりながら走査、再帰を使わない）
+fn collect<T: Clone>(node: &Link<T>, result: &mut Vec<T>) {
+    // (ノード, 反転の偶奇, 左側を処理済みか)
+    let mut stack: Vec<(&RopeNode<T>, bool, bool)> = Vec::new();
+    if let Some(root) = node.as_ref() {
+        stack.push((root, root.rev, false));
+    }
+    while let Some((node, rev, visited)) = stack.pop() {
+        let (first, second) = if rev { (&node.right, &node.left) } else { (&node.left, &node.right) };
+        if visited {
+            result.push(node.value.clone());
+            if let Some(s) = second.as_ref() {
+                stack.push((s, rev ^ s.rev, false));
+            }
+        } else {
+            stack.push((node, rev, true));
+            if let Some(f) = first.as_ref() {
+                stack.push((f, rev ^ f.rev, false));
+            }
+        }
+    }
+}
+
+// Rope（暗黙の treap による列、途中への挿入・削除・区間反転が O(log n)）
+// 文字列の編集をシミュレーションする問題で String::insert の O(n) を避けるのに使う
+pub struct Rope<T> {
+    root: Link<T>,
+}
+
+impl<T: Clone> Rope<T> {
+    pub fn new() -> Self {
+        Rope { root: None }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    // i 番目の要素（反転フラグは根からの偶奇で判断する）
+    pub fn get(&self, mut i: usize) -> T {
+        assert!(i < self.len());
+        let mut node = self.root.as_ref();
+        let mut rev = false;
+        while let Some(n) = node {
+            rev ^= n.rev;
+            let (first, second) = if rev { (&n.right, &n.left) } else { (&n.left, &n.right) };
+            let ls = size(first);
+            if i < ls {
+                node = first.as_ref();
+            } else if i == ls {
+                return n.value.clone();
+            } else {
+                i -= ls + 1;
+                node = second.as_ref();
+            }
+        }
+        unreachable!()
+    }
+
+    // pos の位置に value を挿入する
+    pub fn insert(&mut self, pos: usize, value: T) {
+        assert!(pos <= self.len());
+        let node = Box::new(RopeNode { value, priority: next_priority(), size: 1, rev: false, left: None, right: None });
+        let (l, r) = split(self.root.take(), pos);
+        self.root = merge(merge(l, Some(node)), r);
+    }
+
+    // pos の位置に other 全体を挿入する
+    pub fn insert_rope(&mut self, pos: usize, other: Rope<T>) {
+        assert!(pos <= self.len());
+        let (l, r) = split(self.root.take(), pos);
+        self.root = merge(merge(l, other.root), r);
+    }
+
+    // 末尾に値を追加
+    pub fn push_back(&mut self, value: T) {
+        let len = self.len();
+        self.insert(len, value);
+    }
+
+    // pos の要素を削除して返す
+    pub fn remove(&mut self, pos: usize) -> T {
+        assert!(pos < self.len());
+        let (l, r) = split(self.root.take(), pos);
+        let (mid, r) = split(r, 1);
+        self.root = merge(l, r);
+        mid.unwrap().value
+    }
+
+    // [l, r) を取り除いて、取り除いた部分を Rope として返す
+    pub fn remove_range(&mut self, l: usize, r: usize) -> Rope<T> {
+        assert!(l <= r && r <= self.len());
+        let (a, b) = split(self.root.take(), l);
+        let (mid, c) = split(b, r - l);
+        self.root = merge(a, c);
+        Rope { root: mid }
+    }
+
+    // 末尾に other をつなげる
+    pub fn append(&mut self, other: Rope<T>) {
+        self.root = merge(self.root.take(), other.root);
+    }
+
+    // 先頭 pos 個を残し、残りを Rope として切り離す
+    pub fn split_off(&mut self, pos: usize) -> Rope<T> {
+        assert!(pos <= self.len());
+        let (l, r) = split(self.root.take(), pos);
+        self.root = l;
+        Rope { root: r }
+    }
+
+    // [l, r) を反転する
+    pub fn reverse(&mut self, l: usize, r: usize) {
+        assert!(l <= r && r <= self.len());
+        let (a, b) = split(self.root.take(), l);
+        let (mut mid, c) = split(b, r - l);
+        if let Some(m) = mid.as_mut() {
+            m.rev ^= true;
+        }
+        self.root = merge(merge(a, mid), c);
+    }
+
+    // [l, r) の要素を Vec で取得する（期待 O(log n + (r - l))、[l, r) を切り出してから戻す）
+    pub fn substring(&mut self, l: usize, r: usize) -> Vec<T> {
+        assert!(l <= r && r <= self.len());
+        let (a, b) = split(self.root.take(), l);
+        let (mid, c) = split(b, r - l);
+        let mut result = Vec::with_capacity(r - l);
+        collect(&mid, &mut result);
+        self.root = merge(merge(a, mid), c);
+        result
+    }
+
+    // 全要素を Vec で取得する（O(n)）
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len());
+        collect(&self.root, &mut result);
+        result
+    }
+}
+
+impl<T: Clone> Default for Rope<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> From<Vec<T>> for Rope<T> {
+    fn from(values: Vec<T>) -> Self {
+        let mut rope = Rope::new();
+        for value in values {
+            rope.push_back(value);
+        }
+        rope
+    }
+}
+
+impl From<&str> for Rope<char> {
+    fn from(s: &str) -> Self {
+        Rope::from(s.chars().collect::<Vec<char>>())
+    }
+}
+
+impl From<String> for Rope<char> {
+    fn from(s: String) -> Self {
+        Rope::from(s.as_str())
+    }
+}
+
+impl std::fmt::Display for Rope<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s: String = self.to_vec().into_iter().collect();
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+
+    #[test]
+    fn test_rope_against_vec() {
+        let mut rng = XorShift::default();
+        let mut rope: Rope<usize> = Rope::from(vec![0, 1, 2]);
+        let mut naive: Vec<usize> = vec![0, 1, 2];
+        for step in 0..3000 {
+            let n = naive.len();
+            let l = rng.next(n + 1);
+            let r = l + rng.next(n - l + 1);
+            match rng.next(6) {
+                0 => {
+                    rope.insert(l, step);
+                    naive.insert(l, step);
+                }
+                1 if n > 0 => {
+                    let i = rng.next(n);
+                    assert_eq!(rope.remove(i), naive.remove(i));
+                }
+                2 => {
+                    rope.reverse(l, r);
+                    naive[l..r].reverse();
+                }
+                3 => {
+                    let removed = rope.remove_range(l, r);
+                    let expected: Vec<usize> = naive.drain(l..r).collect();
+                    assert_eq!(removed.to_vec(), expected);
+                    // 取り除いた部分を反転して別の位置に戻す
+                    let mut removed = removed;
+                    let len = removed.len();
+                    removed.reverse(0, len);
+                    let pos = rng.next(naive.len() + 1);
+                    rope.insert_rope(pos, removed);
+                    naive.splice(pos..pos, expected.into_iter().rev());
+                }
+                4 => {
+                    let tail = rope.split_off(l);
+                    let naive_tail = naive.split_off(l);
+                    assert_eq!(tail.to_vec(), naive_tail);
+                    let mut tail = tail;
+                    tail.append(rope);
+                    rope = tail;
+                    naive = naive_tail.into_iter().chain(naive).collect();
+                }
+                _ => {
+                    assert_eq!(rope.substring(l, r), naive[l..r].to_vec());
+                    // 切り出した部分は元の位置に戻っている
+                    assert_eq!(rope.to_vec(), naive);
+                }
+            }
+            assert_eq!(rope.len(), naive.len());
+        }
+        assert_eq!(rope.to_vec(), naive);
+    }
+
+    #[test]
+    fn test_rope_string() {
+        let mut rope = Rope::from("hello world");
+        rope.reverse(0, 5);
+        rope.insert(5, ',');
+        let tail = rope.split_off(6);
+        assert_eq!(rope.to_string(), "olleh,");
+        assert_eq!(tail.to_string(), " world");
+        rope.append(Rope::from(String::from("!")));
+        assert_eq!(rope.to_string(), "olleh,!");
+    }
+}
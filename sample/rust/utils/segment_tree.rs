@@ -0,0 +1,655 @@
+use super::data_structures::Monoid;
+
+// 動的セグメント木のノード（子がない場合は usize::MAX）
+struct DynamicNode<S> {
+    value: S,
+    left: usize,
+    right: usize,
+}
+
+// 動的セグメント木（添字の範囲 [lo, hi) が 10^18 程度でも、触れたノードだけを作成する）
+// 座標圧縮できないオンラインのクエリ向け、未設定の要素は単位元
+pub struct DynamicSegmentTree<M: Monoid> {
+    nodes: Vec<DynamicNode<M::S>>,
+    lo: i64,
+    hi: i64,
+}
+
+impl<M: Monoid> DynamicSegmentTree<M> {
+    // 添字の範囲 [lo, hi) の動的セグメント木を作成（hi - lo が i64 に収まること）
+    pub fn new(lo: i64, hi: i64) -> Self {
+        assert!(lo < hi);
+        assert!(hi.checked_sub(lo).is_some(), "hi - lo must fit in i64");
+        DynamicSegmentTree {
+            nodes: vec![DynamicNode { value: M::identity(), left: usize::MAX, right: usize::MAX }],
+            lo,
+            hi,
+        }
+    }
+
+    fn new_node(&mut self) -> usize {
+        self.nodes.push(DynamicNode { value: M::identity(), left: usize::MAX, right: usize::MAX });
+        self.nodes.len() - 1
+    }
+
+    fn value_of(&self, node: usize) -> M::S {
+        if node == usize::MAX {
+            M::identity()
+        } else {
+            self.nodes[node].value.clone()
+        }
+    }
+
+    // i 番目の要素を x にする（O(log(hi - lo))）
+    pub fn set(&mut self, i: i64, x: M::S) {
+        assert!(self.lo <= i && i < self.hi);
+        let (mut node, mut l, mut r) = (0, self.lo, self.hi);
+        let mut path = vec![];
+        while r - l > 1 {
+            path.push(node);
+            let mid = l + (r - l) / 2;
+            let go_left = i < mid;
+            let child = if go_left { self.nodes[node].left } else { self.nodes[node].right };
+            let child = if child == usize::MAX {
+                let created = self.new_node();
+                if go_left {
+                    self.nodes[node].left = created;
+                } else {
+                    self.nodes[node].right = created;
+                }
+                created
+            } else {
+                child
+            };
+            node = child;
+            if go_left {
+                r = mid;
+            } else {
+                l = mid;
+            }
+        }
+        self.nodes[node].value = x;
+        while let Some(node) = path.pop() {
+            let value = M::op(&self.value_of(self.nodes[node].left), &self.value_of(self.nodes[node].right));
+            self.nodes[node].value = value;
+        }
+    }
+
+    // i 番目の要素を取得
+    pub fn get(&self, i: i64) -> M::S {
+        assert!(self.lo <= i && i < self.hi);
+        let (mut node, mut l, mut r) = (0, self.lo, self.hi);
+        while r - l > 1 {
+            let mid = l + (r - l) / 2;
+            if i < mid {
+                node = self.nodes[node].left;
+                r = mid;
+            } else {
+                node = self.nodes[node].right;
+                l = mid;
+            }
+            if node == usize::MAX {
+                return M::identity();
+            }
+        }
+        self.nodes[node].value.clone()
+    }
+
+    // [l, r) の要素の積（O(log(hi - lo))）
+    pub fn prod(&self, l: i64, r: i64) -> M::S {
+        let l = l.max(self.lo);
+        let r = r.min(self.hi);
+        if l >= r {
+            return M::identity();
+        }
+        self.prod_rec(0, self.lo, self.hi, l, r)
+    }
+
+    fn prod_rec(&self, node: usize, nl: i64, nr: i64, l: i64, r: i64) -> M::S {
+        if node == usize::MAX || r <= nl || nr <= l {
+            return M::identity();
+        }
+        if l <= nl && nr <= r {
+            return self.nodes[node].value.clone();
+        }
+        let mid = nl + (nr - nl) / 2;
+        let left = self.prod_rec(self.nodes[node].left, nl, mid, l, r);
+        let right = self.prod_rec(self.nodes[node].right, mid, nr, l, r);
+        M::op(&left, &right)
+    }
+
+    // 全要素の積
+    pub fn all_prod(&self) -> M::S {
+        self.nodes[0].value.clone()
+    }
+}
+
+// 区間加算に対応した動的セグメント木のノード
+struct RangeAddNode {
+    // 添字の範囲が 10^18 程度でも溢れないように和は i128 で持つ
+    sum: i128,
+    min: i64,
+    max: i64,
+    lazy: i64,
+    left: usize,
+    right: usize,
+}
+
+impl RangeAddNode {
+    fn zero() -> Self {
+        RangeAddNode { sum: 0, min: 0, max: 0, lazy: 0, left: usize::MAX, right: usize::MAX }
+    }
+}
+
+// 区間加算・区間和/最小値/最大値の動的セグメント木（初期値はすべて 0）
+pub struct DynamicRangeAddTree {
+    nodes: Vec<RangeAddNode>,
+    lo: i64,
+    hi: i64,
+}
+
+impl DynamicRangeAddTree {
+    // 添字の範囲 [lo, hi) の木を作成（hi - lo が i64 に収まること）
+    pub fn new(lo: i64, hi: i64) -> Self {
+        assert!(lo < hi);
+        assert!(hi.checked_sub(lo).is_some(), "hi - lo must fit in i64");
+        DynamicRangeAddTree { nodes: vec![RangeAddNode::zero()], lo, hi }
+    }
+
+    fn apply(&mut self, node: usize, len: i64, x: i64) {
+        let n = &mut self.nodes[node];
+        n.sum += x as i128 * len as i128;
+        n.min += x;
+        n.max += x;
+        n.lazy += x;
+    }
+
+    // 子ノードを作成して遅延値を伝播する
+    fn push(&mut self, node: usize, nl: i64, nr: i64) {
+        let mid = nl + (nr - nl) / 2;
+        if self.nodes[node].left == usize::MAX {
+            self.nodes.push(RangeAddNode::zero());
+            self.nodes[node].left = self.nodes.len() - 1;
+        }
+        if self.nodes[node].right == usize::MAX {
+            self.nodes.push(RangeAddNode::zero());
+            self.nodes[node].right = self.nodes.len() - 1;
+        }
+        let lazy = self.nodes[node].lazy;
+        if lazy != 0 {
+            let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+            self.apply(left, mid - nl, lazy);
+            self.apply(right, nr - mid, lazy);
+            self.nodes[node].lazy = 0;
+        }
+    }
+
+    fn pull(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        let (sum, min, max) = {
+            let (a, b) = (&self.nodes[left], &self.nodes[right]);
+            (a.sum + b.sum, a.min.min(b.min), a.max.max(b.max))
+        };
+        let n = &mut self.nodes[node];
+        n.sum = sum;
+        n.min = min;
+        n.max = max;
+    }
+
+    // [l, r) の各要素に x を加える（O(log(hi - lo))）
+    pub fn add(&mut self, l: i64, r: i64, x: i64) {
+        let l = l.max(self.lo);
+        let r = r.min(self.hi);
+        if l < r {
+            self.add_rec(0, self.lo, self.hi, l, r, x);
+        }
+    }
+
+    fn add_rec(&mut self, node: usize, nl: i64, nr: i64, l: i64, r: i64, x: i64) {
+        if r <= nl || nr <= l {
+            return;
+        }
+        if l <= nl && nr <= r {
+            self.apply(node, nr - nl, x);
+            return;
+        }
+        self.push(node, nl, nr);
+        let mid = nl + (nr - nl) / 2;
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.add_rec(left, nl, mid, l, r, x);
+        self.add_rec(right, mid, nr, l, r, x);
+        self.pull(node);
+    }
+
+    // [l, r) の (和, 最小値, 最大値)、空区間なら None
+    fn query(&self, l: i64, r: i64) -> Option<(i128, i64, i64)> {
+        let l = l.max(self.lo);
+        let r = r.min(self.hi);
+        if l >= r {
+            return None;
+        }
+        Some(self.query_rec(0, self.lo, self.hi, l, r, 0))
+    }
+
+    // 子ノードがない区間は、祖先の遅延値 acc がそのまま値になる
+    fn query_rec(&self, node: usize, nl: i64, nr: i64, l: i64, r: i64, acc: i64) -> (i128, i64, i64) {
+        let (l, r) = (l.max(nl), r.min(nr));
+        if node == usize::MAX {
+            return (acc as i128 * (r - l) as i128, acc, acc);
+        }
+        let n = &self.nodes[node];
+        if l == nl && r == nr {
+            return (n.sum + acc as i128 * (r - l) as i128, n.min + acc, n.max + acc);
+        }
+        let mid = nl + (nr - nl) / 2;
+        let acc = acc + n.lazy;
+        if r <= mid {
+            self.query_rec(n.left, nl, mid, l, r, acc)
+        } else if mid <= l {
+            self.query_rec(n.right, mid, nr, l, r, acc)
+        } else {
+            let a = self.query_rec(n.left, nl, mid, l, r, acc);
+            let b = self.query_rec(n.right, mid, nr, l, r, acc);
+            (a.0 + b.0, a.1.min(b.1), a.2.max(b.2))
+        }
+    }
+
+    // [l, r) の和
+    pub fn sum(&self, l: i64, r: i64) -> i128 {
+        self.query(l, r).map_or(0, |q| q.0)
+    }
+
+    // [l, r) の最小値（空区間なら i64::MAX）
+    pub fn min(&self, l: i64, r: i64) -> i64 {
+        self.query(l, r).map_or(i64::MAX, |q| q.1)
+    }
+
+    // [l, r) の最大値（空区間なら i64::MIN）
+    pub fn max(&self, l: i64, r: i64) -> i64 {
+        self.query(l, r).map_or(i64::MIN, |q| q.2)
+    }
+}
+
+// Segment Tree Beats（区間 chmin / chmax / 加算 / 代入と、区間和・最小値・最大値）
+// 各ノードで最大値・2 番目の最大値・最大値の個数（最小値側も同様）を持つ、償却 O(log^2 n)
+pub struct SegmentTreeBeats {
+    n: usize,
+    sum: Vec<i64>,
+    max1: Vec<i64>,
+    max2: Vec<i64>,
+    max_count: Vec<i64>,
+    min1: Vec<i64>,
+    min2: Vec<i64>,
+    min_count: Vec<i64>,
+    len: Vec<i64>,
+    lazy: Vec<i64>,
+}
+
+impl SegmentTreeBeats {
+    // 配列から作成（O(n)、空の配列でもよい）
+    pub fn new(arr: &[i64]) -> Self {
+        let n = arr.len();
+        let size = 4 * n;
+        let mut tree = SegmentTreeBeats {
+            n,
+            sum: vec![0; size],
+            max1: vec![i64::MIN; size],
+            max2: vec![i64::MIN; size],
+            max_count: vec![0; size],
+            min1: vec![i64::MAX; size],
+            min2: vec![i64::MAX; size],
+            min_count: vec![0; size],
+            len: vec![0; size],
+            lazy: vec![0; size],
+        };
+        if n > 0 {
+            tree.build(arr, 1, 0, n);
+        }
+        tree
+    }
+
+    fn build(&mut self, arr: &[i64], k: usize, a: usize, b: usize) {
+        self.len[k] = (b - a) as i64;
+        if b - a == 1 {
+            self.sum[k] = arr[a];
+            self.max1[k] = arr[a];
+            self.min1[k] = arr[a];
+            self.max_count[k] = 1;
+            self.min_count[k] = 1;
+            return;
+        }
+        let mid = (a + b) / 2;
+        self.build(arr, 2 * k, a, mid);
+        self.build(arr, 2 * k + 1, mid, b);
+        self.pull(k);
+    }
+
+    fn pull(&mut self, k: usize) {
+        let (l, r) = (2 * k, 2 * k + 1);
+        self.sum[k] = self.sum[l] + self.sum[r];
+
+        if self.max1[l] == self.max1[r] {
+            self.max1[k] = self.max1[l];
+            self.max2[k] = self.max2[l].max(self.max2[r]);
+            self.max_count[k] = self.max_count[l] + self.max_count[r];
+        } else if self.max1[l] > self.max1[r] {
+            self.max1[k] = self.max1[l];
+            self.max2[k] = self.max2[l].max(self.max1[r]);
+            self.max_count[k] = self.max_count[l];
+        } else {
+            self.max1[k] = self.max1[r];
+            self.max2[k] = self.max1[l].max(self.max2[r]);
+            self.max_count[k] = self.max_count[r];
+        }
+
+        if self.min1[l] == self.min1[r] {
+            self.min1[k] = self.min1[l];
+            self.min2[k] = self.min2[l].min(self.min2[r]);
+            self.min_count[k] = self.min_count[l] + self.min_count[r];
+        } else if self.min1[l] < self.min1[r] {
+            self.min1[k] = self.min1[l];
+            self.min2[k] = self.min2[l].min(self.min1[r]);
+            self.min_count[k] = self.min_count[l];
+        } else {
+            self.min1[k] = self.min1[r];
+            self.min2[k] = self.min1[l].min(self.min2[r]);
+            self.min_count[k] = self.min_count[r];
+        }
+    }
+
+    // ノードの最大値を x に下げる（max2 < x < max1 のとき）
+    fn apply_chmin(&mut self, k: usize, x: i64) {
+        self.sum[k] += (x - self.max1[k]) * self.max_count[k];
+        if self.max1[k] == self.min1[k] {
+            self.min1[k] = x;
+        } else if self.max1[k] == self.min2[k] {
+            self.min2[k] = x;
+        }
+        self.max1[k] = x;
+    }
+
+    // ノードの最小値を x に上げる（min1 < x < min2 のとき）
+    fn apply_chmax(&mut self, k: usize, x: i64) {
+        self.sum[k] += (x - self.min1[k]) * self.min_count[k];
+        if self.min1[k] == self.max1[k] {
+            self.max1[k] = x;
+        } else if self.min1[k] == self.max2[k] {
+            self.max2[k] = x;
+        }
+        self.min1[k] = x;
+    }
+
+    fn apply_add(&mut self, k: usize, x: i64) {
+        self.sum[k] += x * self.len[k];
+        self.max1[k] += x;
+        if self.max2[k] != i64::MIN {
+            self.max2[k] += x;
+        }
+        self.min1[k] += x;
+        if self.min2[k] != i64::MAX {
+            self.min2[k] += x;
+        }
+        self.lazy[k] += x;
+    }
+
+    fn push(&mut self, k: usize) {
+        for c in [2 * k, 2 * k + 1] {
+            if self.lazy[k] != 0 {
+                self.apply_add(c, self.lazy[k]);
+            }
+            if self.max1[c] > self.max1[k] {
+                self.apply_chmin(c, self.max1[k]);
+            }
+            if self.min1[c] < self.min1[k] {
+                self.apply_chmax(c, self.min1[k]);
+            }
+        }
+        self.lazy[k] = 0;
+    }
+
+    // [l, r) の各要素を min(a[i], x) にする
+    pub fn chmin(&mut self, l: usize, r: usize, x: i64) {
+        assert!(l <= r && r <= self.n);
+        self.chmin_rec(l, r, x, 1, 0, self.n);
+    }
+
+    fn chmin_rec(&mut self, l: usize, r: usize, x: i64, k: usize, a: usize, b: usize) {
+        if b <= l || r <= a || self.max1[k] <= x {
+            return;
+        }
+        if l <= a && b <= r && self.max2[k] < x {
+            self.apply_chmin(k, x);
+            return;
+        }
+        self.push(k);
+        let mid = (a + b) / 2;
+        self.chmin_rec(l, r, x, 2 * k, a, mid);
+        self.chmin_rec(l, r, x, 2 * k + 1, mid, b);
+        self.pull(k);
+    }
+
+    // [l, r) の各要素を max(a[i], x) にする
+    pub fn chmax(&mut self, l: usize, r: usize, x: i64) {
+        assert!(l <= r && r <= self.n);
+        self.chmax_rec(l, r, x, 1, 0, self.n);
+    }
+
+    fn chmax_rec(&mut self, l: usize, r: usize, x: i64, k: usize, a: usize, b: usize) {
+        if b <= l || r <= a || self.min1[k] >= x {
+            return;
+        }
+        if l <= a && b <= r && self.min2[k] > x {
+            self.apply_chmax(k, x);
+            return;
+        }
+        self.push(k);
+        let mid = (a + b) / 2;
+        self.chmax_rec(l, r, x, 2 * k, a, mid);
+        self.chmax_rec(l, r, x, 2 * k + 1, mid, b);
+        self.pull(k);
+    }
+
+    // [l, r) の各要素に x を加える
+    pub fn add(&mut self, l: usize, r: usize, x: i64) {
+        assert!(l <= r && r <= self.n);
+        self.add_rec(l, r, x, 1, 0, self.n);
+    }
+
+    fn add_rec(&mut self, l: usize, r: usize, x: i64, k: usize, a: usize, b: usize) {
+        if b <= l || r <= a {
+            return;
+        }
+        if l <= a && b <= r {
+            self.apply_add(k, x);
+            return;
+        }
+        self.push(k);
+        let mid = (a + b) / 2;
+        self.add_rec(l, r, x, 2 * k, a, mid);
+        self.add_rec(l, r, x, 2 * k + 1, mid, b);
+        self.pull(k);
+    }
+
+    // [l, r) の各要素を x にする（chmin と chmax の組み合わせ）
+    pub fn assign(&mut self, l: usize, r: usize, x: i64) {
+        self.chmin(l, r, x);
+        self.chmax(l, r, x);
+    }
+
+    // [l, r) の (和, 最小値, 最大値)
+    fn query(&mut self, l: usize, r: usize, k: usize, a: usize, b: usize) -> (i64, i64, i64) {
+        if b <= l || r <= a {
+            return (0, i64::MAX, i64::MIN);
+        }
+        if l <= a && b <= r {
+            return (self.sum[k], self.min1[k], self.max1[k]);
+        }
+        self.push(k);
+        let mid = (a + b) / 2;
+        let x = self.query(l, r, 2 * k, a, mid);
+        let y = self.query(l, r, 2 * k + 1, mid, b);
+        (x.0 + y.0, x.1.min(y.1), x.2.max(y.2))
+    }
+
+    // [l, r) の和
+    pub fn sum(&mut self, l: usize, r: usize) -> i64 {
+        assert!(l <= r && r <= self.n);
+        self.query(l, r, 1, 0, self.n).0
+    }
+
+    // [l, r) の最小値（空区間なら i64::MAX）
+    pub fn min(&mut self, l: usize, r: usize) -> i64 {
+        assert!(l <= r && r <= self.n);
+        self.query(l, r, 1, 0, self.n).1
+    }
+
+    // [l, r) の最大値（空区間なら i64::MIN）
+    pub fn max(&mut self, l: usize, r: usize) -> i64 {
+        assert!(l <= r && r <= self.n);
+        self.query(l, r, 1, 0, self.n).2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+    use super::super::data_structures::{AddMonoid, MaxMonoid};
+
+    #[test]
+    fn test_dynamic_segment_tree() {
+        // 添字は大きくずらして、実際に使うのは 50 個だけ
+        const OFFSET: i64 = 999_999_999_999_999_000;
+        let mut rng = XorShift::default();
+        let mut sum_tree: DynamicSegmentTree<AddMonoid> = DynamicSegmentTree::new(-OFFSET, OFFSET + 50);
+        let mut max_tree: DynamicSegmentTree<MaxMonoid> = DynamicSegmentTree::new(0, 1_000_000_000_000_000_000);
+        let mut naive = vec![0i64; 50];
+        let mut naive_max = vec![i64::MIN; 50];
+        for _ in 0..2000 {
+            let i = rng.range(0, 50);
+            let x = rng.range(-500, 500);
+            sum_tree.set(OFFSET + i, x);
+            max_tree.set(OFFSET + i, x);
+            naive[i as usize] = x;
+            naive_max[i as usize] = x;
+            let l = rng.range(0, 51);
+            let r = rng.range(l, 51);
+            let expected: i64 = naive[l as usize..r as usize].iter().sum();
+            assert_eq!(sum_tree.prod(OFFSET + l, OFFSET + r), expected);
+            let expected = naive_max[l as usize..r as usize].iter().copied().max().unwrap_or(i64::MIN);
+            assert_eq!(max_tree.prod(OFFSET + l, OFFSET + r), expected);
+            assert_eq!(sum_tree.get(OFFSET + i), x);
+        }
+        assert_eq!(sum_tree.all_prod(), naive.iter().sum::<i64>());
+        assert_eq!(sum_tree.prod(-OFFSET, 0), 0);
+    }
+
+    #[test]
+    fn test_dynamic_range_add_tree() {
+        const N: i64 = 40;
+        const BASE: i64 = -500_000_000_000_000_000;
+        let mut rng = XorShift::new(2463534242);
+        let mut tree = DynamicRangeAddTree::new(BASE, BASE + 1_000_000_000_000_000_000);
+        let mut naive = vec![0i64; N as usize];
+        for _ in 0..3000 {
+            let l = rng.range(0, N);
+            let r = rng.range(l + 1, N + 1);
+            if rng.next(2) == 0 {
+                let x = rng.range(-100, 100);
+                tree.add(BASE + l, BASE + r, x);
+                naive[l as usize..r as usize].iter_mut().for_each(|v| *v += x);
+            } else {
+                let slice = &naive[l as usize..r as usize];
+                assert_eq!(tree.sum(BASE + l, BASE + r), slice.iter().sum::<i64>() as i128);
+                assert_eq!(tree.min(BASE + l, BASE + r), *slice.iter().min().unwrap());
+                assert_eq!(tree.max(BASE + l, BASE + r), *slice.iter().max().unwrap());
+            }
+        }
+        // 一度も触れていない範囲は 0
+        assert_eq!(tree.sum(BASE + N, BASE + N + 1_000_000), 0);
+        assert_eq!(tree.min(BASE + N - 1, BASE + N + 5), naive[N as usize - 1].min(0));
+    }
+
+    #[test]
+    fn test_dynamic_range_add_tree_whole_domain() {
+        const HI: i64 = 1_000_000_000_000_000_000;
+        let mut tree = DynamicRangeAddTree::new(0, HI);
+        tree.add(0, HI, 10);
+        assert_eq!(tree.sum(0, HI), 10 * HI as i128);
+        tree.add(1, HI - 1, -10);
+        tree.add(HI / 2, HI, 7);
+        assert_eq!(tree.sum(0, HI), 10 + 17 + 7 * (HI / 2 - 1) as i128);
+        assert_eq!(tree.sum(HI / 2 - 1, HI / 2 + 1), 7);
+        assert_eq!(tree.min(0, HI), 0);
+        assert_eq!(tree.max(0, HI), 17);
+    }
+
+    #[test]
+    fn test_segment_tree_beats_against_array() {
+        let mut rng = XorShift::new(123456789);
+        for n in [1usize, 2, 7, 64, 100] {
+            let mut naive: Vec<i64> = (0..n).map(|_| rng.range(-100, 100)).collect();
+            let mut tree = SegmentTreeBeats::new(&naive);
+            for _ in 0..3000 {
+                let l = rng.next(n);
+                let r = l + 1 + rng.next(n - l);
+                let x = rng.range(-100, 100);
+                let slice = &mut naive[l..r];
+                match rng.next(7) {
+                    0 => {
+                        tree.chmin(l, r, x);
+                        slice.iter_mut().for_each(|v| *v = (*v).min(x));
+                    }
+                    1 => {
+                        tree.chmax(l, r, x);
+                        slice.iter_mut().for_each(|v| *v = (*v).max(x));
+                    }
+                    2 => {
+                        tree.add(l, r, x);
+                        slice.iter_mut().for_each(|v| *v += x);
+                    }
+                    3 => {
+                        tree.assign(l, r, x);
+                        slice.iter_mut().for_each(|v| *v = x);
+                    }
+                    _ => {
+                        assert_eq!(tree.sum(l, r), slice.iter().sum::<i64>());
+                        assert_eq!(tree.min(l, r), *slice.iter().min().unwrap());
+                        assert_eq!(tree.max(l, r), *slice.iter().max().unwrap());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_segment_tree_beats_empty() {
+        let mut tree = SegmentTreeBeats::new(&[]);
+        tree.chmin(0, 0, 1);
+        tree.chmax(0, 0, 1);
+        tree.add(0, 0, 1);
+        tree.assign(0, 0, 1);
+        assert_eq!(tree.sum(0, 0), 0);
+        assert_eq!(tree.min(0, 0), i64::MAX);
+        assert_eq!(tree.max(0, 0), i64::MIN);
+    }
+
+    #[test]
+    fn test_widest_domains() {
+        let mut tree: DynamicSegmentTree<AddMonoid> = DynamicSegmentTree::new(i64::MIN, -1);
+        tree.set(i64::MIN, 3);
+        tree.set(-2, 4);
+        assert_eq!(tree.prod(i64::MIN, -1), 7);
+        assert_eq!(tree.get(-2), 4);
+        let mut tree = DynamicRangeAddTree::new(0, i64::MAX);
+        tree.add(0, i64::MAX, 2);
+        assert_eq!(tree.sum(0, i64::MAX), 2 * i64::MAX as i128);
+        assert_eq!(tree.max(i64::MAX - 1, i64::MAX), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "hi - lo must fit in i64")]
+    fn test_domain_too_wide() {
+        DynamicSegmentTree::<AddMonoid>::new(i64::MIN, i64::MAX);
+    }
+}
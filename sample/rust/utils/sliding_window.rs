@@ -0,0 +1,240 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+use super::data_structures::Monoid;
+
+// スライド最小値（単調両端キュー）
+// キューとして push / pop しながら、現在の要素の最小値を O(1) で取得する
+pub struct SlidingWindowMin<T: Ord + Clone> {
+    // (追加された番号, 値)、値は先頭から狭義単調増加
+    deque: VecDeque<(usize, T)>,
+    pushed: usize,
+    popped: usize,
+}
+
+impl<T: Ord + Clone> SlidingWindowMin<T> {
+    pub fn new() -> Self {
+        SlidingWindowMin { deque: VecDeque::new(), pushed: 0, popped: 0 }
+    }
+
+    // 末尾に値を追加（償却 O(1)）
+    pub fn push(&mut self, value: T) {
+        while let Some((_, back)) = self.deque.back() {
+            if *back >= value {
+                self.deque.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.deque.push_back((self.pushed, value));
+        self.pushed += 1;
+    }
+
+    // 先頭（最も古い値）を取り除く
+    pub fn pop(&mut self) {
+        assert!(self.popped < self.pushed, "pop from empty window");
+        if let Some(&(id, _)) = self.deque.front() {
+            if id == self.popped {
+                self.deque.pop_front();
+            }
+        }
+        self.popped += 1;
+    }
+
+    // 現在の要素の最小値
+    pub fn min(&self) -> Option<&T> {
+        self.deque.front().map(|(_, v)| v)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pushed - self.popped
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Ord + Clone> Default for SlidingWindowMin<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// スライド最大値（SlidingWindowMin を Reverse で包んだもの）
+pub struct SlidingWindowMax<T: Ord + Clone> {
+    inner: SlidingWindowMin<Reverse<T>>,
+}
+
+impl<T: Ord + Clone> SlidingWindowMax<T> {
+    pub fn new() -> Self {
+        SlidingWindowMax { inner: SlidingWindowMin::new() }
+    }
+
+    // 末尾に値を追加（償却 O(1)）
+    pub fn push(&mut self, value: T) {
+        self.inner.push(Reverse(value));
+    }
+
+    // 先頭（最も古い値）を取り除く
+    pub fn pop(&mut self) {
+        self.inner.pop();
+    }
+
+    // 現在の要素の最大値
+    pub fn max(&self) -> Option<&T> {
+        self.inner.min().map(|Reverse(v)| v)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<T: Ord + Clone> Default for SlidingWindowMax<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 幅 k の各窓の最小値を O(n) で求める（窓は n - k + 1 個）
+pub fn sliding_window_min<T: Ord + Clone>(arr: &[T], k: usize) -> Vec<T> {
+    assert!(k > 0);
+    let mut window = SlidingWindowMin::new();
+    let mut result = Vec::with_capacity(arr.len().saturating_sub(k - 1));
+    for (i, x) in arr.iter().enumerate() {
+        window.push(x.clone());
+        if i >= k {
+            window.pop();
+        }
+        if i + 1 >= k {
+            result.push(window.min().unwrap().clone());
+        }
+    }
+    result
+}
+
+// 幅 k の各窓の最大値を O(n) で求める（窓は n - k + 1 個）
+pub fn sliding_window_max<T: Ord + Clone>(arr: &[T], k: usize) -> Vec<T> {
+    assert!(k > 0);
+    let mut window = SlidingWindowMax::new();
+    let mut result = Vec::with_capacity(arr.len().saturating_sub(k - 1));
+    for (i, x) in arr.iter().enumerate() {
+        window.push(x.clone());
+        if i >= k {
+            window.pop();
+        }
+        if i + 1 >= k {
+            result.push(window.max().unwrap().clone());
+        }
+    }
+    result
+}
+
+// 2 つのスタックによるキュー（Sliding Window Aggregation）
+// 任意のモノイドについて、キュー全体の積を償却 O(1) で取得する
+pub struct FoldableQueue<M: Monoid> {
+    // 取り出し側: (値, その値から底までの積)
+    front: Vec<(M::S, M::S)>,
+    // 追加側の値と、その全体の積
+    back: Vec<M::S>,
+    back_fold: M::S,
+}
+
+impl<M: Monoid> FoldableQueue<M> {
+    pub fn new() -> Self {
+        FoldableQueue { front: Vec::new(), back: Vec::new(), back_fold: M::identity() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 末尾に値を追加
+    pub fn push(&mut self, value: M::S) {
+        self.back_fold = M::op(&self.back_fold, &value);
+        self.back.push(value);
+    }
+
+    // 先頭の値を取り出す（償却 O(1)）
+    pub fn pop(&mut self) -> Option<M::S> {
+        if self.front.is_empty() {
+            // 追加側を逆順に積み替え、各位置から末尾までの積を持たせる
+            let mut acc = M::identity();
+            while let Some(value) = self.back.pop() {
+                acc = M::op(&value, &acc);
+                self.front.push((value, acc.clone()));
+            }
+            self.back_fold = M::identity();
+        }
+        self.front.pop().map(|(value, _)| value)
+    }
+
+    // キュー全体の積（先頭から末尾の順に演算）
+    pub fn fold(&self) -> M::S {
+        match self.front.last() {
+            Some((_, fold)) => M::op(fold, &self.back_fold),
+            None => self.back_fold.clone(),
+        }
+    }
+}
+
+impl<M: Monoid> Default for FoldableQueue<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::data_structures::MinMonoid;
+    use super::super::test_util::Affine;
+
+    #[test]
+    fn test_sliding_window_min_max() {
+        let arr = vec![4, 2, 12, 11, -5, 7, 7, 3, 3, 9];
+        for k in 1..=arr.len() {
+            let mins: Vec<i32> = arr.windows(k).map(|w| *w.iter().min().unwrap()).collect();
+            let maxs: Vec<i32> = arr.windows(k).map(|w| *w.iter().max().unwrap()).collect();
+            assert_eq!(sliding_window_min(&arr, k), mins);
+            assert_eq!(sliding_window_max(&arr, k), maxs);
+        }
+        assert!(sliding_window_min(&arr, arr.len() + 1).is_empty());
+    }
+
+    #[test]
+    fn test_foldable_queue() {
+        let mut queue: FoldableQueue<Affine> = FoldableQueue::new();
+        let mut naive: VecDeque<(u64, u64)> = VecDeque::new();
+        for i in 0..100u64 {
+            let f = (i * 7 % 13 + 1, i * 11 % 17);
+            queue.push(f);
+            naive.push_back(f);
+            if i % 3 == 2 {
+                assert_eq!(queue.pop(), naive.pop_front());
+            }
+            let expected = naive.iter().fold(Affine::identity(), |acc, f| Affine::op(&acc, f));
+            assert_eq!(queue.fold(), expected);
+            assert_eq!(queue.len(), naive.len());
+        }
+
+        let mut mins: FoldableQueue<MinMonoid> = FoldableQueue::new();
+        assert_eq!(mins.fold(), i64::MAX);
+        mins.push(3);
+        mins.push(1);
+        mins.push(2);
+        assert_eq!(mins.fold(), 1);
+        mins.pop();
+        mins.pop();
+        assert_eq!(mins.fold(), 2);
+    }
+}
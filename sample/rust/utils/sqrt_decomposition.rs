@@ -0,0 +1,186 @@
+// 平方分割の 1 ブロック分の処理
+// ブロック全体への更新は遅延タグで持ち、一部分への更新はブロックを作り直す
+pub trait SqrtBlock {
+    type Value;
+    type Update;
+    type Query;
+    type Answer;
+
+    // ブロックの要素からブロックを構築する
+    fn build(values: Vec<Self::Value>) -> Self;
+    // ブロック全体に更新を適用する（遅延タグの更新）
+    fn update_full(&mut self, update: &Self::Update);
+    // ブロック内の [l, r) に更新を適用する（添字はブロック内のもの）
+    fn update_partial(&mut self, l: usize, r: usize, update: &Self::Update);
+    // ブロック全体に対するクエリ
+    fn query_full(&self, query: &Self::Query) -> Self::Answer;
+    // ブロック内の [l, r) に対するクエリ
+    fn query_partial(&self, l: usize, r: usize, query: &Self::Query) -> Self::Answer;
+    // 答えの単位元
+    fn identity() -> Self::Answer;
+    // 2 つのブロックの答えをまとめる
+    fn merge(a: Self::Answer, b: Self::Answer) -> Self::Answer;
+}
+
+// 平方分割（モノイドに載らない区間更新・区間クエリ向け）
+pub struct SqrtDecomposition<B: SqrtBlock> {
+    blocks: Vec<B>,
+    block_size: usize,
+    n: usize,
+}
+
+impl<B: SqrtBlock> SqrtDecomposition<B> {
+    // ブロックサイズ sqrt(n) で作成
+    pub fn new(values: Vec<B::Value>) -> Self {
+        let size = ((values.len() as f64).sqrt() as usize).max(1);
+        Self::with_block_size(values, size)
+    }
+
+    // ブロックサイズを指定して作成
+    pub fn with_block_size(values: Vec<B::Value>, block_size: usize) -> Self {
+        assert!(block_size > 0);
+        let n = values.len();
+        let mut blocks = Vec::with_capacity(n.div_ceil(block_size));
+        let mut iter = values.into_iter().peekable();
+        while iter.peek().is_some() {
+            blocks.push(B::build(iter.by_ref().take(block_size).collect()));
+        }
+        SqrtDecomposition { blocks, block_size, n }
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    // [l, r) と重なる各ブロックについて (ブロック番号, ブロック内の l, ブロック内の r, 全体を覆うか)
+    fn pieces(&self, l: usize, r: usize) -> impl Iterator<Item = (usize, usize, usize, bool)> + '_ {
+        assert!(l <= r && r <= self.n);
+        let first = l / self.block_size;
+        let last = if l == r { first } else { (r - 1) / self.block_size + 1 };
+        (first..last).map(move |b| {
+            let start = b * self.block_size;
+            let end = (start + self.block_size).min(self.n);
+            let (bl, br) = (l.max(start) - start, r.min(end) - start);
+            (b, bl, br, bl == 0 && br == end - start)
+        })
+    }
+
+    // [l, r) に更新を適用する（O(n / B) 回の全体更新 + 2 回の部分更新）
+    pub fn update(&mut self, l: usize, r: usize, update: &B::Update) {
+        let pieces: Vec<_> = self.pieces(l, r).collect();
+        for (b, bl, br, full) in pieces {
+            if full {
+                self.blocks[b].update_full(update);
+            } else {
+                self.blocks[b].update_partial(bl, br, update);
+            }
+        }
+    }
+
+    // [l, r) に対するクエリ
+    pub fn query(&self, l: usize, r: usize, query: &B::Query) -> B::Answer {
+        self.pieces(l, r).fold(B::identity(), |acc, (b, bl, br, full)| {
+            let answer = if full {
+                self.blocks[b].query_full(query)
+            } else {
+                self.blocks[b].query_partial(bl, br, query)
+            };
+            B::merge(acc, answer)
+        })
+    }
+}
+
+// 「区間に x を加算」「区間内で y 以上の要素数」のブロック
+// ブロックごとにソート済みの配列を持ち、全体クエリは二分探索で O(log B)
+pub struct RangeAddCountBlock {
+    values: Vec<i64>,
+    sorted: Vec<i64>,
+    lazy: i64,
+}
+
+impl RangeAddCountBlock {
+    // 遅延タグを要素に反映してソート済み配列を作り直す
+    fn rebuild(&mut self) {
+        if self.lazy != 0 {
+            self.values.iter_mut().for_each(|v| *v += self.lazy);
+            self.lazy = 0;
+        }
+        self.sorted = self.values.clone();
+        self.sorted.sort_unstable();
+    }
+}
+
+impl SqrtBlock for RangeAddCountBlock {
+    type Value = i64;
+    type Update = i64;
+    type Query = i64;
+    type Answer = usize;
+
+    fn build(values: Vec<i64>) -> Self {
+        let mut block = RangeAddCountBlock { values, sorted: vec![], lazy: 0 };
+        block.rebuild();
+        block
+    }
+
+    fn update_full(&mut self, x: &i64) {
+        self.lazy += x;
+    }
+
+    fn update_partial(&mut self, l: usize, r: usize, x: &i64) {
+        self.values[l..r].iter_mut().for_each(|v| *v += x);
+        self.rebuild();
+    }
+
+    fn query_full(&self, y: &i64) -> usize {
+        self.sorted.len() - self.sorted.partition_point(|&v| v + self.lazy < *y)
+    }
+
+    fn query_partial(&self, l: usize, r: usize, y: &i64) -> usize {
+        self.values[l..r].iter().filter(|&&v| v + self.lazy >= *y).count()
+    }
+
+    fn identity() -> usize {
+        0
+    }
+
+    fn merge(a: usize, b: usize) -> usize {
+        a + b
+    }
+}
+
+// 区間加算・区間内の y 以上の要素数（更新・クエリとも O(sqrt(n) log n)）
+pub type RangeAddCountGe = SqrtDecomposition<RangeAddCountBlock>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::XorShift;
+
+    #[test]
+    fn test_range_add_count_ge() {
+        let mut rng = XorShift::default();
+        for (n, block_size) in [(1, 1), (10, 3), (100, 10), (97, 100), (50, 1)] {
+            let mut naive: Vec<i64> = (0..n).map(|_| rng.range(0, 100)).collect();
+            let mut sqrt = RangeAddCountGe::with_block_size(naive.clone(), block_size);
+            for _ in 0..2000 {
+                let l = rng.next(n + 1);
+                let r = l + rng.next(n - l + 1);
+                let x = rng.range(-20, 21);
+                if rng.next(2) == 0 {
+                    sqrt.update(l, r, &x);
+                    naive[l..r].iter_mut().for_each(|v| *v += x);
+                } else {
+                    let y = rng.range(-20, 120);
+                    let expected = naive[l..r].iter().filter(|&&v| v >= y).count();
+                    assert_eq!(sqrt.query(l, r, &y), expected);
+                }
+            }
+        }
+        let default = RangeAddCountGe::new(vec![5, 1, 4]);
+        assert_eq!(default.query(0, 3, &4), 2);
+    }
+}
@@ -0,0 +1,88 @@
+// テスト用の共通部品（乱数、非可換なモノイド、木の素朴な探索）
+use super::data_structures::Monoid;
+use super::graph::{Adjacency, Graph};
+
+// テスト用の簡易乱数（xorshift）
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new(seed: u64) -> Self {
+        XorShift(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // [0, n) の一様乱数
+    pub(crate) fn next(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // [lo, hi) の一様乱数
+    pub(crate) fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + (self.next_u64() % (hi - lo) as u64) as i64
+    }
+}
+
+impl Default for XorShift {
+    fn default() -> Self {
+        XorShift::new(88172645463325252)
+    }
+}
+
+// 一次関数の合成（非可換な演算で順序を確認する）
+pub(crate) struct Affine;
+
+impl Monoid for Affine {
+    // x -> a * x + b（mod 998244353）
+    type S = (u64, u64);
+    fn identity() -> (u64, u64) {
+        (1, 0)
+    }
+    fn op(f: &(u64, u64), g: &(u64, u64)) -> (u64, u64) {
+        const MOD: u64 = 998244353;
+        // f を適用してから g を適用する
+        (f.0 * g.0 % MOD, (f.1 * g.0 + g.1) % MOD)
+    }
+}
+
+// 森の頂点 s から各頂点への (重み付き距離, 辺の本数)（到達できなければ None）
+pub(crate) fn tree_distances(g: &Graph<i64>, s: usize) -> Vec<Option<(i64, u64)>> {
+    let mut dist = vec![None; g.vertex_count()];
+    dist[s] = Some((0, 0));
+    let mut stack = vec![s];
+    while let Some(u) = stack.pop() {
+        let (d, k) = dist[u].unwrap();
+        for e in g.neighbors(u) {
+            if dist[e.to].is_none() {
+                dist[e.to] = Some((d + e.weight, k + 1));
+                stack.push(e.to);
+            }
+        }
+    }
+    dist
+}
+
+// 親の配列（根は usize::MAX）をたどった v から根までの頂点列
+pub(crate) fn path_to_root(parent: &[usize], mut v: usize) -> Vec<usize> {
+    let mut path = vec![v];
+    while parent[v] != usize::MAX {
+        v = parent[v];
+        path.push(v);
+    }
+    path
+}
+
+// 親の配列で表された木での u から v へのパス（両端を含む）
+pub(crate) fn tree_path(parent: &[usize], u: usize, v: usize) -> Vec<usize> {
+    let (pu, pv) = (path_to_root(parent, u), path_to_root(parent, v));
+    let w = *pu.iter().find(|x| pv.contains(x)).unwrap();
+    let mut path: Vec<usize> = pu.iter().copied().take_while(|&x| x != w).collect();
+    path.push(w);
+    path.extend(pv.iter().copied().take_while(|&x| x != w).collect::<Vec<_>>().into_iter().rev());
+    path
+}